@@ -0,0 +1,53 @@
+// Benchmarks each day's parser and both parts against the real inputs in `inputs/`.
+//
+// Save a baseline before a change and compare against it afterwards:
+//   cargo bench --bench days -- --save-baseline before
+//   cargo bench --bench days -- --baseline before
+// A single day can be selected with a filter, e.g. `cargo bench --bench days -- day-6/`
+use std::fs::read_to_string;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use advent_of_code_2024::input::default_input_path;
+use advent_of_code_2024::solution::Solution;
+use advent_of_code_2024::{day_1, day_10, day_11, day_12, day_13, day_2, day_3, day_4, day_5, day_6, day_7, day_8, day_9};
+
+// Days whose slowest part takes longer than a few milliseconds, which would otherwise take minutes to sample
+const SLOW_DAYS: &[u8] = &[6, 7, 9, 12];
+
+fn bench_day<S: Solution>(c: &mut Criterion, day: u8) {
+    let path = default_input_path(day);
+    // Days scaffolded with new-day have no input until it's fetched, which shouldn't stop the other days
+    let Ok(input) = read_to_string(&path) else {
+        eprintln!("Skipping day {}, there's no input at {}", day, path.display());
+        return;
+    };
+    let parsed = S::parse(&input)
+        .unwrap_or_else(|error| panic!("{}", error.with_file(&path)));
+
+    let mut group = c.benchmark_group(format!("day-{}", day));
+    if SLOW_DAYS.contains(&day) {
+        group.sample_size(10);
+    }
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(&input))));
+    group.bench_function("part-1", |b| b.iter(|| S::part_1(black_box(&parsed))));
+    group.bench_function("part-2", |b| b.iter(|| S::part_2(black_box(&parsed))));
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<day_1::Day1>(c, 1);
+    bench_day::<day_2::Day2>(c, 2);
+    bench_day::<day_3::Day3>(c, 3);
+    bench_day::<day_4::Day4>(c, 4);
+    bench_day::<day_5::Day5>(c, 5);
+    bench_day::<day_6::Day6>(c, 6);
+    bench_day::<day_7::Day7>(c, 7);
+    bench_day::<day_8::Day8>(c, 8);
+    bench_day::<day_9::Day9>(c, 9);
+    bench_day::<day_10::Day10>(c, 10);
+    bench_day::<day_11::Day11>(c, 11);
+    bench_day::<day_12::Day12>(c, 12);
+    bench_day::<day_13::Day13>(c, 13);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
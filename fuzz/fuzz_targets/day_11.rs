@@ -0,0 +1,9 @@
+#![no_main]
+
+use advent_of_code_2024::day_11::Day11;
+use advent_of_code_2024::solution::Solution;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = Day11::parse(input);
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use advent_of_code_2024::day_7::Day7;
+use advent_of_code_2024::solution::Solution;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = Day7::parse(input);
+});
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+use crate::input;
+use crate::solution::{Answer, Day};
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Check {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+// What checking one day and part against its input found. Parse errors are already rendered
+// against the input, which isn't kept
+#[derive(Debug, Eq, PartialEq)]
+pub enum Checked {
+    NoInput,
+    ParseError { report: String },
+    Answered { answer: Answer, check: Check },
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct CheckedPart {
+    pub day: u8,
+    pub part: u8,
+    pub checked: Checked,
+}
+
+// Missing inputs count as unknown and parse errors as failures
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct Summary {
+    pub passed: usize,
+    pub failed: usize,
+    pub unknown: usize,
+}
+
+// Answers are stored in a TOML file with a table per day:
+//
+// [day-4]
+// part-1 = 2390
+#[derive(Debug, Default)]
+pub struct KnownAnswers {
+    answers: HashMap<(u8, u8), String>,
+}
+
+impl KnownAnswers {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|error| format!("Unable to read {}: {}", path.display(), error))?;
+        Self::parse(&contents)
+            .map_err(|error| format!("Unable to parse {}: {}", path.display(), error))
+    }
+
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let table = input.parse::<Table>().map_err(|error| error.to_string())?;
+        let mut answers = HashMap::new();
+        for (day_key, parts) in table {
+            let day = parse_key(&day_key, "day-")?;
+            let Value::Table(parts) = parts else {
+                return Err(format!("Expected [{}] to be a table", day_key));
+            };
+            for (part_key, value) in parts {
+                let part = parse_key(&part_key, "part-")?;
+                let expected = match value {
+                    Value::Integer(value) => value.to_string(),
+                    Value::String(value) => value,
+                    other => return Err(format!("Unsupported answer for {}.{}: {}", day_key, part_key, other)),
+                };
+                answers.insert((day, part), expected);
+            }
+        }
+        Ok(KnownAnswers { answers })
+    }
+
+    pub fn check(&self, day: u8, part: u8, answer: &Answer) -> Check {
+        match self.answers.get(&(day, part)) {
+            None => Check::Unknown,
+            Some(expected) if *expected == answer.to_string() => Check::Pass,
+            Some(expected) => Check::Fail { expected: expected.clone() },
+        }
+    }
+}
+
+impl KnownAnswers {
+    // Solves every part of every day against the input at `input_path(day)`, skipping any part a day doesn't have
+    pub fn check_days(&self, days: &[&Day], parts: &[u8], input_path: impl Fn(u8) -> PathBuf) -> Vec<CheckedPart> {
+        days.iter()
+            .flat_map(|day| parts.iter().map(move |&part| (day, part)))
+            .filter_map(|(day, part)| {
+                let checked = self.check_part(day, part, &input_path(day.number))?;
+                Some(CheckedPart { day: day.number, part, checked })
+            })
+            .collect()
+    }
+
+    // None for a part the day doesn't have, which is only found out once there's an input to run it on
+    pub fn check_part(&self, day: &Day, part: u8, input_path: &Path) -> Option<Checked> {
+        let Ok(input) = input::read_input(input_path) else {
+            return Some(Checked::NoInput);
+        };
+        Some(match day.run(&input, part)? {
+            Ok(run) => Checked::Answered { check: self.check(day.number, part, &run.answer), answer: run.answer },
+            Err(error) => Checked::ParseError { report: error.with_file(input_path).report(&input) },
+        })
+    }
+}
+
+impl Summary {
+    pub fn of(checked: &[CheckedPart]) -> Self {
+        let mut summary = Summary::default();
+        for part in checked {
+            match part.checked {
+                Checked::Answered { check: Check::Pass, .. } => summary.passed += 1,
+                Checked::Answered { check: Check::Fail { .. }, .. } | Checked::ParseError { .. } => summary.failed += 1,
+                Checked::Answered { check: Check::Unknown, .. } | Checked::NoInput => summary.unknown += 1,
+            }
+        }
+        summary
+    }
+}
+
+fn parse_key(key: &str, prefix: &str) -> Result<u8, String> {
+    key.strip_prefix(prefix)
+        .and_then(|number| number.parse().ok())
+        .ok_or_else(|| format!("Expected a key like {}N, found '{}'", prefix, key))
+}
+
+pub fn default_answers_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.toml")
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use crate::solution::find_day;
+    use super::*;
+
+    const ANSWERS: &str = r#"
+[day-4]
+part-1 = 2390
+
+[day-9]
+part-2 = "6272188244509"
+"#;
+
+    #[rstest]
+    #[case(4, 1, Answer::U64(2390), Check::Pass)]
+    #[case(4, 1, Answer::U64(18), Check::Fail { expected: "2390".to_string() })]
+    #[case(4, 2, Answer::U64(9), Check::Unknown)]
+    #[case(9, 2, Answer::U64(6272188244509), Check::Pass)]
+    fn test_check(#[case] day: u8, #[case] part: u8, #[case] answer: Answer, #[case] expected: Check) {
+        let answers = KnownAnswers::parse(ANSWERS).unwrap();
+        assert_eq!(answers.check(day, part, &answer), expected);
+    }
+
+    #[rstest]
+    #[case("[4]\npart-1 = 1")]
+    #[case("[day-4]\n1 = 1")]
+    #[case("[day-4]\npart-1 = 1.5")]
+    #[case("day-4 = 1")]
+    fn test_parse_invalid(#[case] input: &str) {
+        assert!(KnownAnswers::parse(input).is_err());
+    }
+
+    #[test]
+    fn test_check_days() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::copy("inputs/day-1-example.txt", dir.path().join("day-1-input.txt")).unwrap();
+        fs::write(dir.path().join("day-2-input.txt"), "1 2 x\n").unwrap();
+        let answers = KnownAnswers::parse("[day-1]\npart-1 = 11\npart-2 = 30\n").unwrap();
+        let days = [1, 2, 3].map(|day| find_day(day).unwrap());
+
+        let checked = answers.check_days(&days, &[1, 2], |day| dir.path().join(format!("day-{}-input.txt", day)));
+        assert_eq!(checked.len(), 6);
+        assert_eq!(checked[0].checked, Checked::Answered { answer: Answer::U64(11), check: Check::Pass });
+        assert_eq!(checked[1].checked, Checked::Answered {
+            answer: Answer::U64(31),
+            check: Check::Fail { expected: "30".to_string() },
+        });
+        assert!(matches!(&checked[2].checked, Checked::ParseError { report } if report.contains("day-2-input.txt:1:5")));
+        assert_eq!((checked[4].day, checked[4].part, &checked[4].checked), (3, 1, &Checked::NoInput));
+        assert_eq!(Summary::of(&checked), Summary { passed: 1, failed: 3, unknown: 2 });
+        assert_eq!(answers.check_part(days[0], 3, &dir.path().join("day-1-input.txt")), None);
+    }
+
+    #[test]
+    fn test_bundled_answers_parse() {
+        assert!(KnownAnswers::load(&default_answers_path()).is_ok());
+    }
+}
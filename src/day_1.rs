@@ -1,41 +1,59 @@
-﻿use std::fs::File;
+﻿use std::cmp::Reverse;
+use std::collections::HashSet;
 use std::io;
 use std::io::BufRead;
+use std::iter::Peekable;
 use itertools::Itertools;
 use regex::Regex;
+use serde::Serialize;
+use crate::external_sort::{ExternalSort, Merged};
+use crate::parse_error::ParseError;
+use crate::solution::{Answer, Solution};
 
-pub fn part_1(input_file: &str) {
-    let (list, other_list) = parse_file(input_file);
-    let result = sum_diffs(&list, &other_list);
-    println!("Sum of diffs: {}", result);
-}
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = (Vec<u32>, Vec<u32>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
 
-pub fn part_2(input_file: &str) {
-    let (list, other_list) = parse_file(input_file);
-    let result = similarity_score(&list, &other_list);
-    println!("Similarity score: {}", result);
+    fn part_1((list, other_list): &Self::Input) -> Answer {
+        sum_diffs(list, other_list).into()
+    }
+
+    fn part_2((list, other_list): &Self::Input) -> Answer {
+        similarity_score(list, other_list).into()
+    }
 }
 
-fn parse_file(input_file: &str) -> (Vec<u32>,Vec<u32>) {
-    let regex = Regex::new(r"(?<left>\d+)\s+(?<right>\d+)").unwrap();
-    let file = File::open(input_file).expect("file not found");
-    let lines: Result<Vec<_>, _> = io::BufReader::new(file).lines().collect();
-    let lines = lines.expect("Error reading lines");
+fn parse_input(input: &str) -> Result<(Vec<u32>,Vec<u32>), ParseError> {
+    let regex = pair_regex();
+    let pairs: Vec<(u32, u32)> = input.lines()
+        .enumerate()
+        .map(|(index, line)| parse_pair(&regex, line).map_err(|error| error.offset_by_lines(index)))
+        .collect::<Result<_, ParseError>>()?;
 
-    let pairs = lines.into_iter().map(|line| {
-        if let Some(captures) = regex.captures(&line) {
-            return (
-                captures["left"].parse::<u32>().unwrap(),
-                captures["right"].parse::<u32>().unwrap()
-            );
-        }
-        panic!("Could not parse line {}", &line);
-    });
+    Ok(pairs.into_iter().unzip())
+}
+
+fn pair_regex() -> Regex {
+    Regex::new(r"(?<left>\d+)\s+(?<right>\d+)").unwrap()
+}
 
-    pairs.unzip()
+fn parse_pair(regex: &Regex, line: &str) -> Result<(u32, u32), ParseError> {
+    let parse_id = |id: &str| id.parse::<u32>()
+        .map_err(|_| ParseError::new("Location ID is too large", line, id));
+    let captures = regex.captures(line)
+        .ok_or_else(|| ParseError::new("Expected two location IDs", line, line))?;
+    Ok((
+        parse_id(captures.name("left").unwrap().as_str())?,
+        parse_id(captures.name("right").unwrap().as_str())?,
+    ))
 }
 
-fn sum_diffs(list: &Vec<u32>, other_list: &Vec<u32>) -> u32 {
+pub fn sum_diffs(list: &[u32], other_list: &[u32]) -> u32 {
     let list_sorted = list.iter()
         .sorted();
     let other_sorted = other_list.iter().sorted();
@@ -45,7 +63,7 @@ fn sum_diffs(list: &Vec<u32>, other_list: &Vec<u32>) -> u32 {
         .sum()
 }
 
-fn similarity_score(list: &Vec<u32>, other_list: &Vec<u32>) -> u32 {
+pub fn similarity_score(list: &[u32], other_list: &[u32]) -> u32 {
     let counts = other_list.iter().counts();
     list.iter()
         .map(|item| {
@@ -55,21 +73,338 @@ fn similarity_score(list: &Vec<u32>, other_list: &Vec<u32>) -> u32 {
         .sum()
 }
 
+// Ways of comparing the two lists beyond the puzzle's own. Results are f64 so every metric fits the
+// same signature, which is exact for the integer metrics up to 2^53
+pub trait ListComparator {
+    fn compare(&self, list: &[u32], other_list: &[u32]) -> f64;
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Metric {
+    L1,
+    L2,
+    KendallTau,
+    Jaccard,
+    Similarity,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 5] = [Metric::L1, Metric::L2, Metric::KendallTau, Metric::Jaccard, Metric::Similarity];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Metric::L1 => "l1",
+            Metric::L2 => "l2",
+            Metric::KendallTau => "kendall-tau",
+            Metric::Jaccard => "jaccard",
+            Metric::Similarity => "similarity",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Metric> {
+        Metric::ALL.into_iter().find(|metric| metric.name() == name)
+    }
+
+    pub fn comparator(&self) -> &'static dyn ListComparator {
+        match self {
+            Metric::L1 => &SortedL1,
+            Metric::L2 => &SortedL2,
+            Metric::KendallTau => &KendallTau,
+            Metric::Jaccard => &Jaccard,
+            Metric::Similarity => &WeightedSimilarity,
+        }
+    }
+}
+
+// The puzzle's part 1: pair the smallest IDs, then the next smallest and so on, and add up the gaps
+pub struct SortedL1;
+
+impl ListComparator for SortedL1 {
+    fn compare(&self, list: &[u32], other_list: &[u32]) -> f64 {
+        sorted_pairs(list, other_list)
+            .map(|(id, other_id)| id.abs_diff(other_id) as u64)
+            .sum::<u64>() as f64
+    }
+}
+
+// The Euclidean distance between the sorted lists, which punishes a few large gaps more than L1
+pub struct SortedL2;
+
+impl ListComparator for SortedL2 {
+    fn compare(&self, list: &[u32], other_list: &[u32]) -> f64 {
+        sorted_pairs(list, other_list)
+            .map(|(id, other_id)| (id.abs_diff(other_id) as f64).powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+}
+
+// The number of pairs of lines whose IDs are in opposite orders in the two lists, so 0 when the
+// lists rank the lines the same way. Ties on either side don't count
+pub struct KendallTau;
+
+impl ListComparator for KendallTau {
+    fn compare(&self, list: &[u32], other_list: &[u32]) -> f64 {
+        // With the lines sorted by the first ID, and ties by the second, a discordant pair is
+        // exactly an inversion among the second IDs
+        let mut others_by_id = list.iter()
+            .zip(other_list)
+            .sorted()
+            .map(|(_, other_id)| *other_id)
+            .collect_vec();
+        count_inversions(&mut others_by_id) as f64
+    }
+}
+
+// The share of distinct IDs that appear in both lists, from 0 for disjoint lists to 1 for the same set
+pub struct Jaccard;
+
+impl ListComparator for Jaccard {
+    fn compare(&self, list: &[u32], other_list: &[u32]) -> f64 {
+        let ids: HashSet<&u32> = list.iter().collect();
+        let other_ids: HashSet<&u32> = other_list.iter().collect();
+        let union = ids.union(&other_ids).count();
+        if union == 0 {
+            return 1.0;
+        }
+        ids.intersection(&other_ids).count() as f64 / union as f64
+    }
+}
+
+// The puzzle's part 2: each ID weighted by how often it appears in the other list
+pub struct WeightedSimilarity;
+
+impl ListComparator for WeightedSimilarity {
+    fn compare(&self, list: &[u32], other_list: &[u32]) -> f64 {
+        let counts = other_list.iter().counts();
+        list.iter()
+            .map(|id| *id as u64 * *counts.get(id).unwrap_or(&0) as u64)
+            .sum::<u64>() as f64
+    }
+}
+
+fn sorted_pairs<'a>(list: &'a [u32], other_list: &'a [u32]) -> impl Iterator<Item = (u32, u32)> + 'a {
+    list.iter().copied().sorted().zip(other_list.iter().copied().sorted())
+}
+
+// Sorts `values` and returns how many pairs i < j had values[i] > values[j]. A merge sort,
+// so long lists stay O(n log n)
+fn count_inversions(values: &mut [u32]) -> u64 {
+    if values.len() < 2 {
+        return 0;
+    }
+    let middle = values.len() / 2;
+    let mut inversions = count_inversions(&mut values[..middle]) + count_inversions(&mut values[middle..]);
+    let (left, right) = values.split_at(middle);
+    let mut merged = Vec::with_capacity(values.len());
+    let (mut i, mut j) = (0, 0);
+    while i < left.len() && j < right.len() {
+        if left[i] <= right[j] {
+            merged.push(left[i]);
+            i += 1;
+        } else {
+            // Every value still waiting on the left is larger than this one from the right
+            inversions += (left.len() - i) as u64;
+            merged.push(right[j]);
+            j += 1;
+        }
+    }
+    merged.extend_from_slice(&left[i..]);
+    merged.extend_from_slice(&right[j..]);
+    values.copy_from_slice(&merged);
+    inversions
+}
+
+// Explains the distance and similarity: the sorted pairs that are furthest apart, the IDs only one
+// list has, and how often each ID appears on each side
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct DiffReport {
+    pub pairs: usize,
+    pub distance: u64,
+    pub top_pairs: Vec<PairDistance>,
+    pub only_in_list: Vec<u32>,
+    pub only_in_other_list: Vec<u32>,
+    pub frequencies: Vec<Frequency>,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct PairDistance {
+    pub id: u32,
+    pub other_id: u32,
+    pub distance: u32,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct Frequency {
+    pub id: u32,
+    pub count: usize,
+    pub other_count: usize,
+    // What this ID adds to the similarity score
+    pub similarity: u64,
+}
+
+// Pairs that are equally far apart stay in sorted order
+pub fn diff_report(list: &[u32], other_list: &[u32], top: usize) -> DiffReport {
+    let pairs = sorted_pairs(list, other_list)
+        .map(|(id, other_id)| PairDistance { id, other_id, distance: id.abs_diff(other_id) })
+        .collect_vec();
+    let counts = list.iter().counts();
+    let other_counts = other_list.iter().counts();
+    let frequencies = counts.keys()
+        .chain(other_counts.keys())
+        .unique()
+        .sorted()
+        .map(|id| {
+            let count = *counts.get(id).unwrap_or(&0);
+            let other_count = *other_counts.get(id).unwrap_or(&0);
+            Frequency {
+                id: **id,
+                count,
+                other_count,
+                similarity: **id as u64 * count as u64 * other_count as u64,
+            }
+        })
+        .collect_vec();
+
+    DiffReport {
+        pairs: pairs.len(),
+        distance: pairs.iter().map(|pair| pair.distance as u64).sum(),
+        only_in_list: frequencies.iter().filter(|frequency| frequency.other_count == 0).map(|frequency| frequency.id).collect(),
+        only_in_other_list: frequencies.iter().filter(|frequency| frequency.count == 0).map(|frequency| frequency.id).collect(),
+        top_pairs: pairs.into_iter().sorted_by_key(|pair| Reverse(pair.distance)).take(top).collect(),
+        frequencies,
+    }
+}
+
+impl DiffReport {
+    pub fn to_text(&self) -> String {
+        let mut text = format!("Top {} of {} sorted pairs, which add up to a distance of {}\n", self.top_pairs.len(), self.pairs, self.distance);
+        text.push_str(&format!("{:>10}  {:>10}  {:>10}\n", "Left", "Right", "Distance"));
+        for pair in &self.top_pairs {
+            text.push_str(&format!("{:>10}  {:>10}  {:>10}\n", pair.id, pair.other_id, pair.distance));
+        }
+        let ids = |ids: &[u32]| if ids.is_empty() { "none".to_string() } else { ids.iter().join(", ") };
+        text.push_str(&format!("\nOnly in the left list ({}): {}\n", self.only_in_list.len(), ids(&self.only_in_list)));
+        text.push_str(&format!("Only in the right list ({}): {}\n", self.only_in_other_list.len(), ids(&self.only_in_other_list)));
+        text.push_str(&format!("\nFrequencies\n{:>10}  {:>10}  {:>10}  {:>10}\n", "ID", "Left", "Right", "Similarity"));
+        for frequency in &self.frequencies {
+            text.push_str(&format!("{:>10}  {:>10}  {:>10}  {:>10}\n", frequency.id, frequency.count, frequency.other_count, frequency.similarity));
+        }
+        text
+    }
+
+    // One table for the whole report, with a section column saying which part each row is from
+    // and blanks in the columns that don't apply to it
+    pub fn to_csv(&self) -> String {
+        let mut csv = "section,id,other_id,distance,count,other_count,similarity\n".to_string();
+        for pair in &self.top_pairs {
+            csv.push_str(&format!("pair,{},{},{},,,\n", pair.id, pair.other_id, pair.distance));
+        }
+        for frequency in &self.frequencies {
+            let section = match (frequency.count, frequency.other_count) {
+                (_, 0) => "only-left",
+                (0, _) => "only-right",
+                _ => "frequency",
+            };
+            csv.push_str(&format!(
+                "{},{},,,{},{},{}\n",
+                section, frequency.id, frequency.count, frequency.other_count, frequency.similarity
+            ));
+        }
+        csv
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Totals {
+    pub distance: u64,
+    pub similarity: u64,
+}
+
+// Works out the same totals as `sum_diffs` and `similarity_score` for lists too long to hold in
+// memory, keeping at most `chunk_size` IDs from each list in memory at once. Totals are u64, since
+// lists this long would overflow a u32
+pub fn stream_totals(reader: impl BufRead, chunk_size: usize) -> Result<Totals, String> {
+    let regex = pair_regex();
+    let mut list = ExternalSort::new(chunk_size);
+    let mut other_list = ExternalSort::new(chunk_size);
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.map_err(|error| format!("Unable to read line {}: {}", index + 1, error))?;
+        let (id, other_id) = parse_pair(&regex, &line)
+            .map_err(|error| error.offset_by_lines(index).to_string())?;
+        list.push(id).map_err(sort_error)?;
+        other_list.push(other_id).map_err(sort_error)?;
+    }
+
+    // Both totals walk the lists in sorted order. The distance pairs IDs up by position,
+    // while the similarity lines up equal IDs, so each takes its own pass over the runs
+    let mut distance = 0;
+    for (id, other_id) in list.merged().map_err(sort_error)?.zip(other_list.merged().map_err(sort_error)?) {
+        distance += id.map_err(sort_error)?.abs_diff(other_id.map_err(sort_error)?) as u64;
+    }
+
+    let mut similarity = 0;
+    let mut ids = list.merged().map_err(sort_error)?.peekable();
+    let mut other_ids = other_list.merged().map_err(sort_error)?.peekable();
+    while let (Some(id), Some(other_id)) = (next_id(&mut ids)?, next_id(&mut other_ids)?) {
+        if id < other_id {
+            ids.next();
+        } else if other_id < id {
+            other_ids.next();
+        } else {
+            let mut count = 0;
+            while next_id(&mut other_ids)? == Some(id) {
+                other_ids.next();
+                count += 1;
+            }
+            while next_id(&mut ids)? == Some(id) {
+                ids.next();
+                similarity += id as u64 * count;
+            }
+        }
+    }
+    Ok(Totals { distance, similarity })
+}
+
+fn sort_error(error: io::Error) -> String {
+    format!("Unable to sort the lists on disk: {}", error)
+}
+
+// The next ID without consuming it, surfacing a read error from the run it came from
+fn next_id(ids: &mut Peekable<Merged>) -> Result<Option<u32>, String> {
+    match ids.peek() {
+        Some(Ok(id)) => Ok(Some(*id)),
+        Some(Err(_)) => Err(sort_error(ids.next().unwrap().unwrap_err())),
+        None => Ok(None),
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::fs::read_to_string;
+    use itertools::Itertools;
+    use proptest::prelude::*;
     use rstest::rstest;
     use super::*;
 
     #[rstest]
     #[case("inputs/day-1-example.txt", (vec![3, 4, 2, 1, 3, 3], vec![4, 3, 5, 3, 9, 3]))]
     fn test_parsing(#[case] input_file: &str, #[case] expected_result: (Vec<u32>, Vec<u32>)) {
-        let (list, other_list) = parse_file(input_file);
+        let (list, other_list) = parse_input(&read_to_string(input_file).unwrap()).unwrap();
         for i in 0..expected_result.0.len() {
             assert_eq!(list[i], expected_result.0[i]);
             assert_eq!(other_list[i], expected_result.1[i]);
         }
     }
 
+    #[rstest]
+    #[case("3   4\n4\n", 2, 1, "4")]
+    #[case("3   4\n4   99999999999\n", 2, 5, "99999999999")]
+    fn test_parsing_error(#[case] input: &str, #[case] line: usize, #[case] column: usize, #[case] text: &str) {
+        let error = parse_input(input).unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (line, column, text));
+    }
+
     #[rstest]
     #[case(vec![3, 4, 2, 1, 3, 3], vec![4, 3, 5, 3, 9, 3], 11)]
     fn test_sum_diffs(#[case] list: Vec<u32>, #[case] other_list: Vec<u32>, #[case] expected_result: u32) {
@@ -83,4 +418,106 @@ mod tests {
         let result = similarity_score(&list, &other_list);
         assert_eq!(result, expected_result);
     }
-}
\ No newline at end of file
+
+    #[rstest]
+    #[case(1)]
+    #[case(4)]
+    #[case(1000)]
+    fn test_stream_totals(#[case] chunk_size: usize) {
+        let input = read_to_string("inputs/day-1-example.txt").unwrap();
+        let totals = stream_totals(input.as_bytes(), chunk_size).unwrap();
+        assert_eq!(totals, Totals { distance: 11, similarity: 31 });
+    }
+
+    #[test]
+    fn test_stream_totals_error() {
+        let error = stream_totals("3   4\n4   3\n2   x\n".as_bytes(), 1).unwrap_err();
+        assert_eq!(error, "<input>:3:1: Expected two location IDs (found \"2   x\")");
+    }
+
+    #[rstest]
+    #[case(Metric::L1, 11.0)]
+    #[case(Metric::L2, 35f64.sqrt())]
+    #[case(Metric::KendallTau, 5.0)]
+    #[case(Metric::Jaccard, 2.0 / 6.0)]
+    #[case(Metric::Similarity, 31.0)]
+    fn test_comparators(#[case] metric: Metric, #[case] expected: f64) {
+        let (list, other_list) = parse_input(&read_to_string("inputs/day-1-example.txt").unwrap()).unwrap();
+        assert_eq!(metric.comparator().compare(&list, &other_list), expected);
+        assert_eq!(Metric::from_name(metric.name()), Some(metric));
+    }
+
+    #[rstest]
+    #[case(vec![], vec![], 0.0, 1.0)]
+    #[case(vec![1, 2, 3], vec![1, 2, 3], 0.0, 1.0)]
+    #[case(vec![1, 2, 3], vec![6, 5, 4], 3.0, 0.0)]
+    fn test_comparator_extremes(#[case] list: Vec<u32>, #[case] other_list: Vec<u32>, #[case] kendall_tau: f64, #[case] jaccard: f64) {
+        assert_eq!(KendallTau.compare(&list, &other_list), kendall_tau);
+        assert_eq!(Jaccard.compare(&list, &other_list), jaccard);
+    }
+
+    #[test]
+    fn test_diff_report() {
+        let (list, other_list) = parse_input(&read_to_string("inputs/day-1-example.txt").unwrap()).unwrap();
+        let report = diff_report(&list, &other_list, 2);
+        assert_eq!((report.pairs, report.distance), (6, 11));
+        assert_eq!(report.top_pairs, vec![
+            PairDistance { id: 4, other_id: 9, distance: 5 },
+            PairDistance { id: 1, other_id: 3, distance: 2 },
+        ]);
+        assert_eq!((report.only_in_list, report.only_in_other_list), (vec![1, 2], vec![5, 9]));
+        assert_eq!(report.frequencies.iter().map(|frequency| frequency.similarity).sum::<u64>(), 31);
+        assert_eq!(report.frequencies[2], Frequency { id: 3, count: 3, other_count: 3, similarity: 27 });
+    }
+
+    #[test]
+    fn test_diff_report_csv() {
+        let report = diff_report(&[3, 4, 7], &[4, 3, 3], 1);
+        assert_eq!(report.to_csv(), "\
+section,id,other_id,distance,count,other_count,similarity
+pair,7,4,3,,,
+frequency,3,,,1,2,6
+frequency,4,,,1,1,4
+only-left,7,,,1,0,0
+");
+    }
+
+    #[test]
+    fn test_diff_report_text() {
+        let text = diff_report(&[3, 4, 7], &[4, 3, 3], 1).to_text();
+        assert!(text.starts_with("Top 1 of 3 sorted pairs, which add up to a distance of 4\n"), "{}", text);
+        assert!(text.contains("Only in the left list (1): 7\nOnly in the right list (0): none\n"), "{}", text);
+        assert!(text.contains(&format!("{:>10}  {:>10}  {:>10}  {:>10}\n", 3, 1, 2, 6)), "{}", text);
+    }
+
+    fn count_discordant_pairs(list: &[u32], other_list: &[u32]) -> u64 {
+        (0..list.len())
+            .tuple_combinations()
+            .filter(|&(i, j)| {
+                (list[i] < list[j] && other_list[i] > other_list[j])
+                    || (list[i] > list[j] && other_list[i] < other_list[j])
+            })
+            .count() as u64
+    }
+
+    proptest! {
+        // Few distinct IDs and tiny chunks, so that IDs repeat across many runs
+        #[test]
+        fn test_stream_totals_match_in_memory(
+            pairs in prop::collection::vec((0u32..20, 0u32..20), 0..50),
+            chunk_size in 1usize..8,
+        ) {
+            let (list, other_list): (Vec<u32>, Vec<u32>) = pairs.iter().cloned().unzip();
+            let input = pairs.iter().map(|(id, other_id)| format!("{}   {}", id, other_id)).join("\n");
+            let totals = stream_totals(input.as_bytes(), chunk_size).unwrap();
+            prop_assert_eq!(totals.distance, sum_diffs(&list, &other_list) as u64);
+            prop_assert_eq!(totals.similarity, similarity_score(&list, &other_list) as u64);
+        }
+
+        #[test]
+        fn test_kendall_tau_matches_brute_force(pairs in prop::collection::vec((0u32..10, 0u32..10), 0..40)) {
+            let (list, other_list): (Vec<u32>, Vec<u32>) = pairs.into_iter().unzip();
+            prop_assert_eq!(KendallTau.compare(&list, &other_list), count_discordant_pairs(&list, &other_list) as f64);
+        }
+    }
+}
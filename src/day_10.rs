@@ -1,82 +1,51 @@
-use std::collections::{HashMap, HashSet, VecDeque};
-use std::fs::File;
-use std::io;
-use std::io::BufRead;
+use std::collections::{HashSet, VecDeque};
+use crate::grid::{Grid, Point};
+use crate::parse_error::ParseError;
+use crate::solution::{Answer, Solution};
 
-pub fn part_1(input_file: &str) {
-    let map = parse_file(input_file);
-    let result: usize = map.trail_heads
-        .iter()
-        .map(|trailhead| score(trailhead, &map.heights))
-        .sum();
-    println!("Total: {}", result);
-}
+pub struct Day10;
 
-pub fn part_2(input_file: &str) {
-    let map = parse_file(input_file);
-    let result: usize = map.trail_heads
-        .iter()
-        .map(|trailhead| score_v2(trailhead, &map.heights))
-        .sum();
-    println!("Total: {}", result);
-}
+impl Solution for Day10 {
+    type Input = Map;
 
-#[derive(Debug)]
-struct Map {
-    heights: HashMap<Point, u32>,
-    trail_heads: Vec<Point>,
-}
-
-#[derive(Debug, Eq, PartialEq, Hash, Clone)]
-struct Point {
-    x: i64,
-    y: i64,
-}
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
 
-impl Point {
-    fn neighbours(&self) -> Vec<Point> {
-        vec![
-            Point { x: -1, y: 0 },
-            Point { x: 1, y: 0 },
-            Point { x: 0, y: -1 },
-            Point { x: 0, y: 1 },
-        ].into_iter()
-            .map(|diff| self.add(&diff))
-            .collect()
+    fn part_1(map: &Self::Input) -> Answer {
+        map.trail_heads
+            .iter()
+            .map(|trailhead| score(trailhead, &map.heights))
+            .sum::<usize>().into()
     }
 
-    fn add(&self, other: &Point) -> Point {
-        Point {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        }
+    fn part_2(map: &Self::Input) -> Answer {
+        map.trail_heads
+            .iter()
+            .map(|trailhead| score_v2(trailhead, &map.heights))
+            .sum::<usize>().into()
     }
 }
 
-fn parse_file(file_name: &str) -> Map {
-    let file = File::open(file_name).expect("file not found");
-    let lines: Result<Vec<_>, _> = io::BufReader::new(file).lines().collect();
-    let lines = lines.expect("Error reading lines");
+#[derive(Debug)]
+pub struct Map {
+    heights: Grid<u32>,
+    trail_heads: Vec<Point>,
+}
 
-    let mut heights = HashMap::new();
-    let mut trail_heads = Vec::new();
-    for (y, line) in lines.into_iter().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            let point = Point { x: x as i64, y: y as i64 };
-            let height = c.to_digit(10).unwrap();
-            heights.insert(point.clone(), height);
-            if height == 0 {
-                trail_heads.push(point);
-            }
-        }
-    }
-    Map {
+fn parse_input(input: &str) -> Result<Map, ParseError> {
+    let heights = Grid::parse(input, |c| c.to_digit(10))?;
+    let trail_heads = heights.iter()
+        .filter(|(_, height)| **height == 0)
+        .map(|(point, _)| point)
+        .collect();
+    Ok(Map {
         heights,
         trail_heads,
-    }
+    })
 }
 
-fn score(trailhead: &Point, map: &HashMap<Point, u32>) -> usize {
+fn score(trailhead: &Point, map: &Grid<u32>) -> usize {
     let mut explore_queue = VecDeque::new();
     explore_queue.push_back((trailhead.clone(), 1));
     let mut seen = HashSet::new();
@@ -90,7 +59,7 @@ fn score(trailhead: &Point, map: &HashMap<Point, u32>) -> usize {
             peaks += 1;
             continue;
         }
-        for neighbour in point.neighbours() {
+        for neighbour in map.neighbours(&point) {
             if map.get(&neighbour) == Some(&next_height) && !seen.contains(&neighbour) {
                 explore_queue.push_back((neighbour, next_height + 1));
             }
@@ -99,7 +68,7 @@ fn score(trailhead: &Point, map: &HashMap<Point, u32>) -> usize {
     peaks
 }
 
-fn score_v2(trailhead: &Point, map: &HashMap<Point, u32>) -> usize {
+fn score_v2(trailhead: &Point, map: &Grid<u32>) -> usize {
     let mut explore_queue = VecDeque::new();
     explore_queue.push_back((trailhead.clone(), 1));
     let mut peaks = 0;
@@ -108,7 +77,7 @@ fn score_v2(trailhead: &Point, map: &HashMap<Point, u32>) -> usize {
             peaks += 1;
             continue;
         }
-        for neighbour in point.neighbours() {
+        for neighbour in map.neighbours(&point) {
             if map.get(&neighbour) == Some(&next_height) {
                 explore_queue.push_back((neighbour, next_height + 1));
             }
@@ -119,12 +88,13 @@ fn score_v2(trailhead: &Point, map: &HashMap<Point, u32>) -> usize {
 
 #[cfg(test)]
 mod tests {
+    use std::fs::read_to_string;
     use itertools::Itertools;
     use super::*;
 
     #[test]
     fn test_example() {
-        let map = parse_file("inputs/day-10-example.txt");
+        let map = parse_input(&read_to_string("inputs/day-10-example.txt").unwrap()).unwrap();
         let result = map.trail_heads
             .iter()
             .map(|trail_head| score(trail_head, &map.heights))
@@ -137,7 +107,7 @@ mod tests {
 
     #[test]
     fn test_example_v2() {
-        let map = parse_file("inputs/day-10-example.txt");
+        let map = parse_input(&read_to_string("inputs/day-10-example.txt").unwrap()).unwrap();
         let result = map.trail_heads
             .iter()
             .map(|trail_head| score_v2(trail_head, &map.heights))
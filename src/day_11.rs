@@ -1,30 +1,45 @@
 use std::collections::HashMap;
 use itertools::Itertools;
+use crate::parse_error::ParseError;
+use crate::solution::{Answer, Solution};
 
-pub fn part_1(input_file: &str) {
-    let mut stones = parse_file(input_file);
-    for _ in 0..25 {
-        stones = blink(stones);
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = HashMap<u64, usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
-    println!("{} stones total after 25 blinks", stones.values().sum::<usize>());
-}
 
-pub fn part_2(input_file: &str) {
-    let mut stones = parse_file(input_file);
-    for _ in 0..75 {
-        stones = blink(stones);
+    fn part_1(stones: &Self::Input) -> Answer {
+        let mut stones = stones.clone();
+        for _ in 0..25 {
+            stones = blink(stones);
+        }
+        stones.values().sum::<usize>().into()
+    }
+
+    fn part_2(stones: &Self::Input) -> Answer {
+        let mut stones = stones.clone();
+        for _ in 0..75 {
+            stones = blink(stones);
+        }
+        stones.values().sum::<usize>().into()
     }
-    println!("{} stones total after 75 blinks", stones.values().sum::<usize>());
 }
 
-fn parse_file(input_file: &str) -> HashMap<u64, usize> {
-    let line = std::fs::read_to_string(input_file).expect("cannot read file");
-    line.split_whitespace()
-        .map(|number| number.parse::<u64>().expect("cannot parse number"))
-        .counts()
+fn parse_input(input: &str) -> Result<HashMap<u64, usize>, ParseError> {
+    let stones = input.split_whitespace()
+        .map(|number| {
+            number.parse::<u64>()
+                .map_err(|_| ParseError::new("Expected a stone number", input, number))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(stones.into_iter().counts())
 }
 
-fn blink(stones: HashMap<u64, usize>) -> HashMap<u64, usize> {
+pub fn blink(stones: HashMap<u64, usize>) -> HashMap<u64, usize> {
     let mut result = HashMap::new();
     for (number, count) in stones.iter() {
         let number_string = format!("{}", number);
@@ -1,72 +1,44 @@
 use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io;
-use std::io::BufRead;
 use itertools::Itertools;
+use crate::grid::{Grid, Point};
+use crate::parse_error::ParseError;
+use crate::solution::{Answer, Solution};
 
-pub fn part_1(input_file: &str) {
-    let map = parse_file(input_file);
-    let regions = calculate_regions(&map);
-    let fence_price: usize = regions.into_iter()
-        .map(|region| region.area * region.fences)
-        .sum();
-    println!("Fence price: {}", fence_price);
-}
+pub struct Day12;
 
-pub fn part_2(input_file: &str) {
-    let map = parse_file(input_file);
-    let regions = calculate_regions(&map);
-    let fence_price: usize = regions.into_iter()
-        .map(|region| region.area * region.edges)
-        .sum();
-    println!("Fence price: {}", fence_price);
-}
+impl Solution for Day12 {
+    type Input = Map;
 
-#[derive(Debug, Eq, PartialEq, Hash, Clone)]
-struct Point {
-    x: i64,
-    y: i64,
-}
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
 
-impl Point {
-    fn neighbours(&self) -> Vec<Point> {
-        vec![
-            Point { x: -1, y: 0 },
-            Point { x: 1, y: 0 },
-            Point { x: 0, y: -1 },
-            Point { x: 0, y: 1 },
-        ].into_iter()
-            .map(|diff| self.add(&diff))
-            .collect()
+    fn part_1(map: &Self::Input) -> Answer {
+        let regions = calculate_regions(map);
+        regions.into_iter()
+            .map(|region| region.area * region.fences)
+            .sum::<usize>().into()
     }
 
-    fn add(&self, other: &Point) -> Point {
-        Point {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        }
+    fn part_2(map: &Self::Input) -> Answer {
+        let regions = calculate_regions(map);
+        regions.into_iter()
+            .map(|region| region.area * region.edges)
+            .sum::<usize>().into()
     }
 }
 
-fn parse_file(file_name: &str) -> Map {
-    let file = File::open(file_name).expect("file not found");
-    let lines: Result<Vec<_>, _> = io::BufReader::new(file).lines().collect();
-    let lines = lines.expect("Error reading lines");
-
-    let mut map = Map {
-        points_by_plant: HashMap::new(),
-        plants_at_point: HashMap::new(),
-    };
-
-    for (y, line) in lines.into_iter().enumerate() {
-        for  (x, c) in line.chars().enumerate() {
-            map.plants_at_point.insert(Point { x: x as i64, y: y as i64 }, c);
-            map.points_by_plant.entry(c)
-                .or_insert(Vec::new())
-                .push(Point { x: x as i64, y: y as i64 });
-        }
+fn parse_input(input: &str) -> Result<Map, ParseError> {
+    let plants = Grid::parse_chars(input)?;
+    let mut points_by_plant: HashMap<char, Vec<Point>> = HashMap::new();
+    for (point, plant) in plants.iter() {
+        points_by_plant.entry(*plant)
+            .or_default()
+            .push(point);
     }
-    map
+    Ok(Map {
+        points_by_plant,
+    })
 }
 
 fn count_fences(region: &HashSet<Point>) -> usize {
@@ -83,14 +55,8 @@ fn count_fences(region: &HashSet<Point>) -> usize {
 fn count_edges(region: &HashSet<Point>) -> usize {
     let fences = region.iter()
         .flat_map(|point| {
-            let diffs = vec![
-                Point { x: -1, y: 0 },
-                Point { x: 1, y: 0 },
-                Point { x: 0, y: -1 },
-                Point { x: 0, y: 1 },
-            ];
-            diffs.into_iter()
-                .filter(|diff| !region.contains(&point.add(&diff)))
+            Point::ORTHOGONAL_OFFSETS.into_iter()
+                .filter(|diff| !region.contains(&point.add(diff)))
                 .map(|diff| (diff, point.clone()))
         })
         .into_group_map();
@@ -106,8 +72,8 @@ fn count_edges(region: &HashSet<Point>) -> usize {
                 .map(|location| (location.y, location.x))
                 .into_group_map()
         };
-        let n = groups.into_iter()
-            .map(|(_, values)| {
+        let n = groups.into_values()
+            .map(|values| {
                 if values.len() == 1 {
                     return 1;
                 }
@@ -130,8 +96,7 @@ fn count_edges(region: &HashSet<Point>) -> usize {
 }
 
 #[derive(Debug)]
-struct Map {
-    plants_at_point: HashMap<Point, char>,
+pub struct Map {
     points_by_plant: HashMap<char, Vec<Point>>,
 }
 
@@ -190,7 +155,7 @@ fn merge_regions_step(mut regions: Vec<HashSet<Point>>) -> (bool, Vec<HashSet<Po
                 other_pointer += 1;
             } else {
                 merge_occured = true;
-                region.extend(other_region.into_iter());
+                region.extend(other_region);
                 regions.remove(other_pointer);
             }
         }
@@ -202,11 +167,12 @@ fn merge_regions_step(mut regions: Vec<HashSet<Point>>) -> (bool, Vec<HashSet<Po
 
 #[cfg(test)]
 mod tests {
+    use std::fs::read_to_string;
     use super::*;
 
     #[test]
     fn test_calculate_regions_example_1() {
-        let map = parse_file("inputs/day-12-example-1.txt");
+        let map = parse_input(&read_to_string("inputs/day-12-example-1.txt").unwrap()).unwrap();
         let regions = calculate_regions(&map);
         assert!(regions.contains(&RegionStats { plant: 'A', area: 4, fences: 10, edges: 4 }));
         assert!(regions.contains(&RegionStats { plant: 'B', area: 4, fences: 8, edges: 4 }));
@@ -217,7 +183,7 @@ mod tests {
 
     #[test]
     fn test_calculate_regions_example_2() {
-        let map = parse_file("inputs/day-12-example-2.txt");
+        let map = parse_input(&read_to_string("inputs/day-12-example-2.txt").unwrap()).unwrap();
         let regions = calculate_regions(&map);
         assert!(regions.contains(&RegionStats { plant: 'O', area: 21, fences: 36, edges: 20 }));
         let count = regions.iter()
@@ -228,7 +194,7 @@ mod tests {
 
     #[test]
     fn test_calculate_regions_example_3() {
-        let map = parse_file("inputs/day-12-example-3.txt");
+        let map = parse_input(&read_to_string("inputs/day-12-example-3.txt").unwrap()).unwrap();
         let regions = calculate_regions(&map);
         assert!(regions.contains(&RegionStats { plant: 'R', area: 12, fences: 18, edges: 10 }));
         assert!(regions.contains(&RegionStats { plant: 'I', area: 4, fences: 8, edges: 4 }));
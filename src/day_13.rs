@@ -1,26 +1,36 @@
-use itertools::Itertools;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use regex::Regex;
+use crate::parallel;
+use crate::parse_error::{line_of, ParseError};
+use crate::solution::{Answer, Solution};
 
-pub fn part_1(input_file: &str) {
-    let machines = parse_file(input_file);
-    let tokens: u64 = machines.iter()
-        .filter_map(Machine::solve)
-        .map(|(a, b)| a * 3 + b)
-        .sum();
-    println!("Tokens Spent: {}", tokens);
-}
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<Machine>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(machines: &Self::Input) -> Answer {
+        parallel::iter(machines)
+            .filter_map(Machine::solve)
+            .map(|(a, b)| a * 3 + b)
+            .sum::<u64>().into()
+    }
 
-pub fn part_2(input_file: &str) {
-    let machines = parse_file(input_file);
-    let tokens: u64 = machines.iter()
-        .filter_map(Machine::solve_part_2)
-        .map(|(a, b)| a * 3 + b)
-        .sum();
-    println!("Tokens Spent: {}", tokens);
+    fn part_2(machines: &Self::Input) -> Answer {
+        parallel::iter(machines)
+            .filter_map(Machine::solve_part_2)
+            .map(|(a, b)| a * 3 + b)
+            .sum::<u64>().into()
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
-struct Machine {
+pub struct Machine {
     button_a: Button,
     button_b: Button,
     prize: (u64, u64),
@@ -33,7 +43,7 @@ struct Button {
 }
 
 impl Machine {
-    fn solve(self: &Self) -> Option<(u64, u64)>
+    pub fn solve(&self) -> Option<(u64, u64)>
     {
         let b_result: f64 = (
             (self.prize.0 as f64 * self.button_a.y as f64)
@@ -42,97 +52,160 @@ impl Machine {
             (self.button_b.x as f64 * self.button_a.y as f64)
                 - (self.button_b.y as f64 * self.button_a.x as f64)
         );
-        if b_result <= 0.0 || b_result > 100.0 || b_result.fract() != 0.0 {
+        if !(0.0..=100.0).contains(&b_result) || b_result.fract() != 0.0 {
             return None;
         }
         let a_result = (self.prize.0 as f64 - self.button_b.x as f64 * b_result) / self.button_a.x as f64;
-        if a_result <= 0.0 || a_result > 100.0 || a_result.fract() != 0.0 {
+        if !(0.0..=100.0).contains(&a_result) || a_result.fract() != 0.0 {
             return None;
         }
         Some((a_result as u64, b_result as u64))
     }
 
-    fn solve_part_2(self: &Self) -> Option<(u64, u64)>
+    pub fn solve_part_2(&self) -> Option<(u64, u64)>
     {
         let prize_x = self.prize.0 as f64 + 10000000000000.0;
         let prize_y = self.prize.1 as f64 + 10000000000000.0;
 
         let b_result: f64 = (
-            (prize_x as f64 * self.button_a.y as f64)
-                - (prize_y as f64 * self.button_a.x as f64)
+            (prize_x * self.button_a.y as f64)
+                - (prize_y * self.button_a.x as f64)
         ) / (
             (self.button_b.x as f64 * self.button_a.y as f64)
                 - (self.button_b.y as f64 * self.button_a.x as f64)
         );
-        if b_result <= 0.0 || b_result.fract() != 0.0 {
+        if b_result < 0.0 || b_result.fract() != 0.0 {
             return None;
         }
-        let a_result = (prize_x as f64 - self.button_b.x as f64 * b_result) / self.button_a.x as f64;
-        if a_result <= 0.0 || a_result.fract() != 0.0 {
+        let a_result = (prize_x - self.button_b.x as f64 * b_result) / self.button_a.x as f64;
+        if a_result < 0.0 || a_result.fract() != 0.0 {
             return None;
         }
         Some((a_result as u64, b_result as u64))
     }
 }
 
-fn parse_file(file_name: &str) -> Vec<Machine> {
-    let input = std::fs::read_to_string(&file_name)
-        .expect(&format!("Failed to open {}", file_name));
-    input.split("\n\n")
-        .map(|section| section.lines().collect_vec())
-        .map(|section| parse_machine(&section))
+fn parse_input(input: &str) -> Result<Vec<Machine>, ParseError> {
+    // Machines are split on blank lines rather than "\n\n", so inputs with Windows line endings split too
+    let mut sections = Vec::new();
+    let mut start = None;
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        if line.trim_end_matches(['\r', '\n']).is_empty() {
+            sections.extend(start.take().map(|start| &input[start..offset]));
+        } else {
+            start.get_or_insert(offset);
+        }
+        offset += line.len();
+    }
+    sections.extend(start.map(|start| &input[start..]));
+
+    sections.into_iter()
+        .map(|section| parse_machine(section).map_err(|error| error.offset_by_lines(line_of(input, section) - 1)))
         .collect()
 }
 
-fn parse_machine(lines: &Vec<&str>) -> Machine {
-    let button_regex = Regex::new(r"Button (?<button>.+): X\+(?<x>\d+), Y\+(?<y>\d+)").unwrap();
-    let prize_regex = Regex::new(r"Prize: X=(?<x>\d+), Y=(?<y>\d+)").unwrap();
+fn parse_machine(section: &str) -> Result<Machine, ParseError> {
+    let button_regex = Regex::new(r"^Button (?<button>.+): X\+(?<x>\d+), Y\+(?<y>\d+)$").unwrap();
+    let prize_regex = Regex::new(r"^Prize: X=(?<x>\d+), Y=(?<y>\d+)$").unwrap();
 
-    let captures = button_regex.captures(lines[0])
-        .expect(&format!("Failed to capture button {}", lines[0]));
+    // A lone '\r' is left on a last line with no newline after it
+    let mut lines = section.lines().map(|line| line.strip_suffix('\r').unwrap_or(line));
+    let mut next_line = |expected: &str| {
+        lines.next()
+            .ok_or_else(|| ParseError::new(format!("Expected {}", expected), section, &section[section.len()..]))
+    };
+    let parse_number = |line: &str, captures: &regex::Captures, name: &str| {
+        let number = &line[captures.name(name).unwrap().range()];
+        number.parse()
+            .map_err(|_| ParseError::new("Number is too large", section, number))
+    };
+
+    let line = next_line("a line like 'Button A: X+94, Y+34'")?;
+    let captures = button_regex.captures(line)
+        .ok_or_else(|| ParseError::new("Failed to capture button", section, line))?;
     let button_a = Button {
-        x: captures["x"].parse().expect("Failed to parse number"),
-        y: captures["y"].parse().expect("Failed to parse number"),
+        x: parse_number(line, &captures, "x")?,
+        y: parse_number(line, &captures, "y")?,
     };
 
-    let captures = button_regex.captures(lines[1])
-        .expect(&format!("Failed to capture button {}", lines[1]));
+    let line = next_line("a line like 'Button B: X+22, Y+67'")?;
+    let captures = button_regex.captures(line)
+        .ok_or_else(|| ParseError::new("Failed to capture button", section, line))?;
     let button_b = Button {
-        x: captures["x"].parse().expect("Failed to parse number"),
-        y: captures["y"].parse().expect("Failed to parse number"),
+        x: parse_number(line, &captures, "x")?,
+        y: parse_number(line, &captures, "y")?,
     };
 
-    let captures = prize_regex.captures(lines[2])
-        .expect(&format!("Failed to capture prize {}", lines[2]));
+    let line = next_line("a line like 'Prize: X=8400, Y=5400'")?;
+    let captures = prize_regex.captures(line)
+        .ok_or_else(|| ParseError::new("Failed to capture prize", section, line))?;
     let prize = (
-        captures["x"].parse().expect("Failed to parse number"),
-        captures["y"].parse().expect("Failed to parse number"),
+        parse_number(line, &captures, "x")?,
+        parse_number(line, &captures, "y")?,
     );
 
-    Machine {
+    if let Some(line) = lines.next() {
+        return Err(ParseError::new("Expected a blank line before the next machine", section, line));
+    }
+
+    Ok(Machine {
         button_a,
         button_b,
         prize,
-    }
+    })
 }
 
 #[cfg(test)]
 mod tests {
+    use std::fs::read_to_string;
+    use proptest::prelude::*;
+    use rstest::rstest;
     use super::*;
 
     #[test]
     fn test_parse_machine() {
-        let input = vec![
-            "Button A: X+94, Y+34",
-            "Button B: X+22, Y+67",
-            "Prize: X=8400, Y=5400",
-        ];
+        let input = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400";
         let expected = Machine {
             button_a: Button { x: 94, y: 34 },
             button_b: Button { x: 22, y: 67 },
             prize: (8400, 5400),
         };
-        assert_eq!(parse_machine(&input), expected);
+        assert_eq!(parse_machine(input), Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_file_truncated() {
+        let input = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\n\nButton A: X+26, Y+66\nButton B: X+67, Y+21";
+        let error = parse_input(input).unwrap_err();
+        assert_eq!((error.line, error.column), (6, 21));
+        assert_eq!(error.message, "Expected a line like 'Prize: X=8400, Y=5400'");
+    }
+
+    #[rstest]
+    #[case("\n", "")]
+    #[case("\r\n", "")]
+    #[case("\r\n", "\r")]
+    fn test_parse_file_line_endings(#[case] line_ending: &str, #[case] end: &str) {
+        let input = read_to_string("inputs/day-13-example.txt").unwrap().replace('\n', line_ending) + end;
+        let machines = parse_input(&input).unwrap();
+        assert_eq!(machines.len(), 4);
+        assert_eq!(Day13::part_1(&machines), Answer::U64(480));
+    }
+
+    #[test]
+    fn test_parse_file_missing_blank_line() {
+        let input = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\nButton A: X+26, Y+66\n";
+        let error = parse_input(input).unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (4, 1, "Button A: X+26, Y+66"));
+        assert_eq!(error.message, "Expected a blank line before the next machine");
+    }
+
+    #[test]
+    fn test_parse_file_bad_button() {
+        let input = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\n\nButton A: X+26, Y-66";
+        let error = parse_input(input).unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (5, 1, "Button A: X+26, Y-66"));
     }
 
     #[test]
@@ -192,7 +265,7 @@ mod tests {
             button_b: Button { x: 67, y: 21 },
             prize: (12748, 12176),
         };
-        assert_eq!(machine.solve_part_2().is_some(), true);
+        assert!(machine.solve_part_2().is_some());
     }
 
     #[test]
@@ -212,6 +285,62 @@ mod tests {
             button_b: Button { x: 27, y: 71 },
             prize: (18641, 10279),
         };
-        assert_eq!(machine.solve_part_2().is_some(), true);
+        assert!(machine.solve_part_2().is_some());
+    }
+
+    // Not pressing one of the buttons at all is still a way to win the prize
+    #[rstest]
+    #[case((94, 34), (22, 67), (2200, 6700), Some((0, 100)))]
+    #[case((94, 34), (22, 67), (940, 340), Some((10, 0)))]
+    #[case((94, 34), (22, 67), (0, 0), Some((0, 0)))]
+    fn test_solve_zero_presses(#[case] a: (u64, u64), #[case] b: (u64, u64), #[case] prize: (u64, u64), #[case] expected: Option<(u64, u64)>) {
+        let machine = Machine { button_a: Button { x: a.0, y: a.1 }, button_b: Button { x: b.0, y: b.1 }, prize };
+        assert_eq!(machine.solve(), expected);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_solve_part_2_zero_presses() {
+        let machine = Machine { button_a: Button { x: 1, y: 2 }, button_b: Button { x: 1, y: 1 }, prize: (0, 0) };
+        assert_eq!(machine.solve_part_2(), Some((0, 10000000000000)));
+    }
+
+    // Every combination of up to 100 presses of each button, keeping the cheapest that reaches the prize
+    fn cheapest_presses(machine: &Machine) -> Option<(u64, u64)> {
+        (0..=100u64)
+            .flat_map(|a| (0..=100u64).map(move |b| (a, b)))
+            .filter(|(a, b)| {
+                a * machine.button_a.x + b * machine.button_b.x == machine.prize.0
+                    && a * machine.button_a.y + b * machine.button_b.y == machine.prize.1
+            })
+            .min_by_key(|(a, b)| a * 3 + b)
+    }
+
+    fn machines() -> impl Strategy<Value = Machine> {
+        let buttons = (1..100u64, 1..100u64, 1..100u64, 1..100u64)
+            // Buttons moving in the same direction have many solutions, which the puzzle inputs avoid
+            .prop_filter("buttons must not be parallel", |(ax, ay, bx, by)| ax * by != ay * bx);
+        let presses = prop_oneof![
+            // Reachable prizes, which random ones almost never are
+            (0..=100u64, 0..=100u64).prop_map(Some),
+            Just(None),
+        ];
+        (buttons, presses, 0..20000u64, 0..20000u64).prop_map(|((ax, ay, bx, by), presses, x, y)| {
+            let prize = match presses {
+                Some((a, b)) => (a * ax + b * bx, a * ay + b * by),
+                None => (x, y),
+            };
+            Machine {
+                button_a: Button { x: ax, y: ay },
+                button_b: Button { x: bx, y: by },
+                prize,
+            }
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_solve_matches_brute_force(machine in machines()) {
+            prop_assert_eq!(machine.solve(), cheapest_presses(&machine));
+        }
+    }
+}
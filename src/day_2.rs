@@ -1,91 +1,210 @@
-﻿use std::fs::File;
-use std::io;
-use std::io::BufRead;
+﻿use std::cmp::Reverse;
+use std::fs;
+use std::path::Path;
 use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use crate::parse_error::ParseError;
+use crate::solution::{Answer, Solution};
 
-pub fn part_1(input_file: &str) {
-    let reports = parse_file(input_file);
-    let result = reports.into_iter().filter(is_safe)
-        .count();
-    println!("Safe reports: {}", result);
-}
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Report>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(reports: &Self::Input) -> Answer {
+        reports.iter().filter(|report| is_safe(report, &SafetyPolicy::default()))
+            .count().into()
+    }
 
-pub fn part_2(input_file: &str) {
-    let reports = parse_file(input_file);
-    let result = reports.into_iter().filter(is_safe_with_problem_dampener)
-        .count();
-    println!("Safe reports: {}", result);
+    fn part_2(reports: &Self::Input) -> Answer {
+        reports.iter().filter(|report| is_safe_with_problem_dampener(report, &SafetyPolicy::default()))
+            .count().into()
+    }
 }
 
-type Report = Vec<i64>;
+pub type Report = Vec<i64>;
 
-fn parse_file(file_name: &str) -> Vec<Report>
+fn parse_input(input: &str) -> Result<Vec<Report>, ParseError>
 {
-    let file = File::open(file_name).expect("file not found");
-    let lines: Result<Vec<_>, _> = io::BufReader::new(file).lines().collect();
-    let lines = lines.expect("Error reading lines");
-    lines.into_iter()
+    input.lines()
         .map(|line| {
             line.split_whitespace()
                 .map(|reading| {
-                    reading.trim().parse()
-                        .expect(&format!("Failed to parse number '{}'", reading))
+                    reading.parse()
+                        .map_err(|_| ParseError::new("Expected a level", input, reading))
                 })
-                .collect_vec()
+                .collect()
         })
-        .collect_vec()
+        .collect()
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AllowedDirection {
+    Either,
+    Increasing,
+    Decreasing,
+}
+
+impl AllowedDirection {
+    pub const ALL: [AllowedDirection; 3] = [AllowedDirection::Either, AllowedDirection::Increasing, AllowedDirection::Decreasing];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AllowedDirection::Either => "either",
+            AllowedDirection::Increasing => "increasing",
+            AllowedDirection::Decreasing => "decreasing",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<AllowedDirection> {
+        AllowedDirection::ALL.into_iter().find(|direction| direction.name() == name)
+    }
+
+    fn allows(&self, sign: i64) -> bool {
+        match self {
+            AllowedDirection::Either => true,
+            AllowedDirection::Increasing => sign > 0,
+            AllowedDirection::Decreasing => sign < 0,
+        }
+    }
 }
 
-fn is_safe(report: &Report) -> bool
+// What makes a report safe. Every step between adjacent levels must be between `min_step` and
+// `max_step` in size and go in an allowed direction, and if `strictly_monotone` is set the levels
+// can't change direction part way. Steps of 0, allowed when `min_step` is 0, count as either direction.
+// The default is the puzzle's own policy. As TOML, any field left out keeps its default:
+//
+//   min-step = 0
+//   max-step = 5
+//   strictly-monotone = true
+//   direction = "increasing"
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct SafetyPolicy {
+    pub min_step: u64,
+    pub max_step: u64,
+    pub strictly_monotone: bool,
+    pub direction: AllowedDirection,
+}
+
+impl Default for SafetyPolicy {
+    fn default() -> Self {
+        SafetyPolicy {
+            min_step: 1,
+            max_step: 3,
+            strictly_monotone: true,
+            direction: AllowedDirection::Either,
+        }
+    }
+}
+
+impl SafetyPolicy {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|error| format!("Unable to read {}: {}", path.display(), error))?;
+        let policy: SafetyPolicy = toml::from_str(&contents)
+            .map_err(|error| format!("Unable to parse {}: {}", path.display(), error))?;
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_step > self.max_step {
+            return Err(format!("The minimum step {} is larger than the maximum step {}", self.min_step, self.max_step));
+        }
+        Ok(())
+    }
+
+    // The directions a safe report could run in. A monotone report picks one and sticks to it, which
+    // is None when the levels are free to change direction
+    fn trends(&self) -> Vec<Option<i64>> {
+        if self.strictly_monotone {
+            [1, -1].into_iter()
+                .filter(|sign| self.direction.allows(*sign))
+                .map(Some)
+                .collect()
+        } else {
+            vec![None]
+        }
+    }
+
+    fn allows_step(&self, step: i64, trend: Option<i64>) -> bool {
+        let sign = step.signum();
+        (self.min_step..=self.max_step).contains(&step.unsigned_abs())
+            && (sign == 0 || (self.direction.allows(sign) && trend.is_none_or(|trend| trend == sign)))
+    }
+}
+
+pub fn is_safe(report: &Report, policy: &SafetyPolicy) -> bool
 {
-    let diffs = report.iter()
-        .tuple_windows::<(_, _)>()
-        .map(|window| window.1 - window.0)
-        .collect_vec();
-    diffs.iter().all(|&diff| i64::abs(diff) > 0 && i64::abs(diff) < 4)
-    && diffs.iter().map(|diff| diff.signum()).all_equal()
+    policy.trends().into_iter().any(|trend| {
+        report.iter()
+            .tuple_windows()
+            .all(|(level, next_level)| policy.allows_step(next_level - level, trend))
+    })
 }
 
-fn is_safe_with_problem_dampener(report: &Report) -> bool
+pub fn is_safe_with_problem_dampener(report: &Report, policy: &SafetyPolicy) -> bool
 {
-    let diffs = report.iter()
-        .tuple_windows::<(_, _)>()
-        .map(|window| window.1 - window.0)
-        .collect_vec();
+    is_safe_with_tolerance(report, 1, policy).is_some()
+}
 
-    if diffs.is_empty() {
-        return true;
+// The fewest levels to remove, at most `tolerance` of them, that leave the report safe, as indexes in
+// ascending order. Rather than trying every subset, this finds the longest run of levels that could
+// stay, for each trend, by extending the best run ending at every earlier level: O(n^2)
+pub fn is_safe_with_tolerance(report: &Report, tolerance: usize, policy: &SafetyPolicy) -> Option<Vec<usize>>
+{
+    let kept = policy.trends().into_iter()
+        .map(|trend| longest_safe_subsequence(report, |step| policy.allows_step(step, trend)))
+        .max_by_key(|kept| kept.len())
+        .unwrap_or_default();
+    if report.len() - kept.len() > tolerance {
+        return None;
     }
+    Some((0..report.len()).filter(|index| !kept.contains(index)).collect())
+}
 
-    let expected_sign = diffs[0].signum();
+// Indexes of the longest subsequence whose steps are all allowed
+fn longest_safe_subsequence(report: &Report, allows_step: impl Fn(i64) -> bool) -> Vec<usize>
+{
+    // For each level, the length of the longest safe run ending there and the level before it in that run
+    let mut longest: Vec<(usize, Option<usize>)> = Vec::with_capacity(report.len());
+    for (index, level) in report.iter().enumerate() {
+        let best_previous = (0..index)
+            .filter(|&previous| allows_step(level - report[previous]))
+            .max_by_key(|&previous| (longest[previous].0, Reverse(previous)));
+        longest.push(match best_previous {
+            Some(previous) => (longest[previous].0 + 1, Some(previous)),
+            None => (1, None),
+        });
+    }
 
-    for (index, diff) in diffs.iter().enumerate() {
-        if i64::abs(*diff) < 1 || i64::abs(*diff) > 3 || diff.signum() != expected_sign {
-            let mut indexes_to_try_removing = vec![index, index + 1];
-            if index > 0 {
-                indexes_to_try_removing.push(index - 1);
-            }
-            let dampened_reports = indexes_to_try_removing.into_iter()
-                .map(|index_to_remove| {
-                    let mut dampened_readings = report.clone();
-                    dampened_readings.remove(index_to_remove);
-                    dampened_readings
-                }).collect_vec();
-            return dampened_reports.iter().any(|report| is_safe(report));
-        }
+    let mut end = (0..report.len()).max_by_key(|&index| (longest[index].0, Reverse(index)));
+    let mut kept = Vec::new();
+    while let Some(index) = end {
+        kept.push(index);
+        end = longest[index].1;
     }
-    return true;
+    kept.reverse();
+    kept
 }
 
 #[cfg(test)]
 mod tests {
+    use std::fs::read_to_string;
+    use proptest::prelude::*;
     use rstest::rstest;
     use super::*;
 
     #[test]
     fn test_parse_file()
     {
-        let result = parse_file("inputs/day-2-example.txt");
+        let result = parse_input(&read_to_string("inputs/day-2-example.txt").unwrap()).unwrap();
         assert_eq!(result.len(), 6);
         assert_eq!(result[0], vec![7, 6, 4, 2, 1]);
         assert_eq!(result[1], vec![1, 2, 7, 8, 9]);
@@ -104,7 +223,7 @@ mod tests {
     #[case(vec![1, 3, 6, 7, 9], true)]
     fn test_is_safe(#[case] report: Report, #[case] expected: bool)
     {
-        assert_eq!(is_safe(&report), expected);
+        assert_eq!(is_safe(&report, &SafetyPolicy::default()), expected);
     }
 
     #[rstest]
@@ -116,6 +235,127 @@ mod tests {
     #[case(vec![1, 3, 6, 7, 9], true)]
     fn test_is_safe_with_problem_dampener(#[case] report: Report, #[case] expected: bool)
     {
-        assert_eq!(is_safe_with_problem_dampener(&report), expected);
+        assert_eq!(is_safe_with_problem_dampener(&report, &SafetyPolicy::default()), expected);
+    }
+
+    // The brute-force version of the dampener: try the report as is, then without each level in turn
+    fn is_safe_removing_any_single_level(report: &Report) -> bool {
+        let policy = SafetyPolicy::default();
+        is_safe(report, &policy) || (0..report.len()).any(|index| {
+            let mut dampened = report.clone();
+            dampened.remove(index);
+            is_safe(&dampened, &policy)
+        })
+    }
+
+    fn policy(min_step: u64, max_step: u64, strictly_monotone: bool, direction: AllowedDirection) -> SafetyPolicy {
+        SafetyPolicy { min_step, max_step, strictly_monotone, direction }
+    }
+
+    #[rstest]
+    #[case(vec![1, 1, 2, 3], SafetyPolicy::default(), false)]
+    #[case(vec![1, 1, 2, 3], policy(0, 3, true, AllowedDirection::Either), true)]
+    #[case(vec![1, 6, 10], SafetyPolicy::default(), false)]
+    #[case(vec![1, 6, 10], policy(1, 5, true, AllowedDirection::Either), true)]
+    #[case(vec![1, 3, 2, 4], SafetyPolicy::default(), false)]
+    #[case(vec![1, 3, 2, 4], policy(1, 3, false, AllowedDirection::Either), true)]
+    #[case(vec![1, 3, 2, 4], policy(1, 3, false, AllowedDirection::Increasing), false)]
+    #[case(vec![5, 4, 3], policy(1, 3, true, AllowedDirection::Increasing), false)]
+    #[case(vec![5, 4, 3], policy(1, 3, true, AllowedDirection::Decreasing), true)]
+    #[case(vec![2, 2, 2], policy(0, 3, true, AllowedDirection::Increasing), true)]
+    #[case(vec![2, 3, 2], policy(0, 3, true, AllowedDirection::Either), false)]
+    fn test_is_safe_with_policy(#[case] report: Report, #[case] policy: SafetyPolicy, #[case] expected: bool)
+    {
+        assert_eq!(is_safe(&report, &policy), expected);
+    }
+
+    #[test]
+    fn test_is_safe_with_tolerance_and_policy()
+    {
+        let plateaus = policy(0, 3, true, AllowedDirection::Either);
+        assert_eq!(is_safe_with_tolerance(&vec![1, 1, 2, 9, 3], 1, &plateaus), Some(vec![3]));
+        assert_eq!(is_safe_with_tolerance(&vec![1, 1, 2, 9, 3], 1, &SafetyPolicy::default()), None);
+    }
+
+    #[test]
+    fn test_load_policy()
+    {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.toml");
+        fs::write(&path, "max-step = 5\ndirection = \"increasing\"\n").unwrap();
+        assert_eq!(SafetyPolicy::load(&path).unwrap(), policy(1, 5, true, AllowedDirection::Increasing));
+
+        fs::write(&path, "min-step = 4\n").unwrap();
+        assert!(SafetyPolicy::load(&path).unwrap_err().contains("larger than the maximum"));
+        fs::write(&path, "max-steps = 4\n").unwrap();
+        assert!(SafetyPolicy::load(&path).unwrap_err().contains("unknown field"));
+    }
+
+    #[rstest]
+    #[case(vec![1, 3, 6, 7, 9], 0, Some(vec![]))]
+    #[case(vec![1, 3, 2, 4, 5], 0, None)]
+    #[case(vec![1, 3, 2, 4, 5], 1, Some(vec![2]))]
+    #[case(vec![1, 2, 7, 8, 9], 1, None)]
+    #[case(vec![1, 2, 7, 8, 9], 2, Some(vec![0, 1]))]
+    #[case(vec![9, 1, 7, 6, 20, 5, 4], 2, Some(vec![1, 4]))]
+    #[case(vec![], 0, Some(vec![]))]
+    fn test_is_safe_with_tolerance(#[case] report: Report, #[case] tolerance: usize, #[case] expected: Option<Vec<usize>>)
+    {
+        assert_eq!(is_safe_with_tolerance(&report, tolerance, &SafetyPolicy::default()), expected);
+    }
+
+    // The fewest levels that have to go for the report to be safe, trying every subset of each size
+    fn fewest_removals_by_brute_force(report: &Report, policy: &SafetyPolicy) -> usize {
+        (0..=report.len())
+            .find(|&removals| {
+                (0..report.len()).combinations(removals).any(|removed| {
+                    let remaining = report.iter()
+                        .enumerate()
+                        .filter(|(index, _)| !removed.contains(index))
+                        .map(|(_, level)| *level)
+                        .collect_vec();
+                    is_safe(&remaining, policy)
+                })
+            })
+            .unwrap()
     }
-}
\ No newline at end of file
+
+    fn policies() -> impl Strategy<Value = SafetyPolicy> {
+        (0u64..3, 0u64..4, any::<bool>(), prop::sample::select(AllowedDirection::ALL.to_vec()))
+            .prop_map(|(min_step, extra_step, strictly_monotone, direction)| SafetyPolicy {
+                min_step,
+                max_step: min_step + extra_step,
+                strictly_monotone,
+                direction,
+            })
+    }
+
+    proptest! {
+        // Small levels and short reports make both safe and unsafe reports likely
+        #[test]
+        fn test_dampener_matches_brute_force(report in prop::collection::vec(1i64..10, 0..8)) {
+            prop_assert_eq!(is_safe_with_problem_dampener(&report, &SafetyPolicy::default()), is_safe_removing_any_single_level(&report));
+        }
+
+        #[test]
+        fn test_tolerance_matches_brute_force(
+            report in prop::collection::vec(1i64..10, 0..9),
+            tolerance in 0usize..4,
+            policy in policies(),
+        ) {
+            let fewest = fewest_removals_by_brute_force(&report, &policy);
+            match is_safe_with_tolerance(&report, tolerance, &policy) {
+                Some(removed) => {
+                    prop_assert_eq!(removed.len(), fewest);
+                    let remaining = report.iter()
+                        .enumerate()
+                        .filter(|(index, _)| !removed.contains(index))
+                        .map(|(_, level)| *level)
+                        .collect_vec();
+                    prop_assert!(is_safe(&remaining, &policy));
+                }
+                None => prop_assert!(fewest > tolerance),
+            }
+        }
+    }
+}
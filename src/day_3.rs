@@ -1,16 +1,38 @@
 use itertools::Itertools;
 use regex::Regex;
+use crate::parse_error::ParseError;
+use crate::solution::{Answer, Solution};
 
-pub fn part_1(input_file: &str) {
-    let instructions = parse_instructions(input_file);
-    let result = instructions.into_iter()
-        .filter_map(|instruction| match instruction {
-            Instruction::Multiply(mult_instruction) => Some(mult_instruction),
-            _ => None,
-        })
-        .map(evaluate_mult)
-        .sum::<u64>();
-    println!("Sum of multiplications: {}", result);
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_instructions(input)
+    }
+
+    fn part_1(instructions: &Self::Input) -> Answer {
+        instructions.iter()
+            .filter_map(|instruction| match instruction {
+                Instruction::Multiply(mult_instruction) => Some(mult_instruction),
+                _ => None,
+            })
+            .map(evaluate_mult)
+            .sum::<u64>().into()
+    }
+
+    fn part_2(instructions: &Self::Input) -> Answer {
+        let result = instructions.iter()
+            .fold(
+                EvaluationState {
+                    total: 0,
+                    enabled: true,
+                },
+                evaluate
+            );
+        result.total.into()
+    }
 }
 
 struct EvaluationState {
@@ -18,33 +40,20 @@ struct EvaluationState {
     enabled: bool,
 }
 
-pub fn part_2(input_file: &str) {
-    let instructions = parse_instructions(input_file);
-    let result = instructions.into_iter()
-        .fold(
-            EvaluationState {
-                total: 0,
-                enabled: true,
-            },
-            evaluate
-        );
-    println!("Sum of multiplications: {}", result.total);
-}
-
 #[derive(Debug, Eq, PartialEq)]
-struct MultiplyInstruction {
+pub struct MultiplyInstruction {
     left: u64,
     right: u64
 }
 
 #[derive(Debug, Eq, PartialEq)]
-enum Instruction {
+pub enum Instruction {
     Multiply(MultiplyInstruction),
     Do,
     Dont,
 }
 
-fn evaluate(state: EvaluationState, instruction: Instruction) -> EvaluationState {
+fn evaluate(state: EvaluationState, instruction: &Instruction) -> EvaluationState {
     match instruction {
         Instruction::Multiply(multiply_instruction) => {
             if state.enabled {
@@ -71,11 +80,11 @@ fn evaluate(state: EvaluationState, instruction: Instruction) -> EvaluationState
     }
 }
 
-fn evaluate_mult(instruction: MultiplyInstruction) -> u64 {
+fn evaluate_mult(instruction: &MultiplyInstruction) -> u64 {
     instruction.left * instruction.right
 }
 
-fn parse_instructions(input_file: &str) -> Vec<Instruction> {
+fn parse_instructions(input: &str) -> Result<Vec<Instruction>, ParseError> {
     let multiply_regex = Regex::new(r"mul\((?<left>\d+),(?<right>\d+)\)").unwrap();
     let do_regex = Regex::new(r"do\(\)").unwrap();
     let dont_regex = Regex::new(r"don't\(\)").unwrap();
@@ -86,22 +95,27 @@ fn parse_instructions(input_file: &str) -> Vec<Instruction> {
         .collect_vec()
         .join("|")).unwrap();
 
-    let input = std::fs::read_to_string(&input_file).expect("Unable to read file");
-    instruction_regex.find_iter(&input)
+    instruction_regex.find_iter(input)
         .map(|instruction| {
             if let Some(captures) = multiply_regex.captures(instruction.as_str()) {
-                return Instruction::Multiply(MultiplyInstruction {
-                    left: captures["left"].parse().unwrap(),
-                    right: captures["right"].parse().unwrap()
-                });
+                let parse_operand = |name: &str| {
+                    let start = instruction.start() + captures.name(name).unwrap().start();
+                    let operand = &input[start..start + captures[name].len()];
+                    operand.parse()
+                        .map_err(|_| ParseError::new("Operand is too large", input, operand))
+                };
+                return Ok(Instruction::Multiply(MultiplyInstruction {
+                    left: parse_operand("left")?,
+                    right: parse_operand("right")?,
+                }));
             }
 
             if do_regex.captures(instruction.as_str()).is_some() {
-                return Instruction::Do;
+                return Ok(Instruction::Do);
             }
 
             if dont_regex.captures(instruction.as_str()).is_some() {
-                return Instruction::Dont;
+                return Ok(Instruction::Dont);
             }
 
             panic!("Unknown instruction: {:?}", instruction);
@@ -111,6 +125,7 @@ fn parse_instructions(input_file: &str) -> Vec<Instruction> {
 
 #[cfg(test)]
 mod tests {
+    use std::fs::read_to_string;
     use crate::day_3::*;
 
     #[test]
@@ -121,7 +136,7 @@ mod tests {
             Instruction::Multiply(MultiplyInstruction { left: 11, right: 8 }),
             Instruction::Multiply(MultiplyInstruction { left: 8, right: 5 }),
         ];
-        assert_eq!(parse_instructions("inputs/day-3-example.txt"), expected);
+        assert_eq!(parse_instructions(&read_to_string("inputs/day-3-example.txt").unwrap()).unwrap(), expected);
     }
 
     #[test]
@@ -134,6 +149,6 @@ mod tests {
             Instruction::Do,
             Instruction::Multiply(MultiplyInstruction { left: 8, right: 5 }),
         ];
-        assert_eq!(parse_instructions("inputs/day-3-part-2-example.txt"), expected);
+        assert_eq!(parse_instructions(&read_to_string("inputs/day-3-part-2-example.txt").unwrap()).unwrap(), expected);
     }
 }
\ No newline at end of file
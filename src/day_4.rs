@@ -1,124 +1,94 @@
-use std::fs::File;
-use std::io;
-use std::io::BufRead;
-use itertools::Itertools;
+use std::slice;
+use crate::grid::{Grid, Point};
+use crate::parse_error::ParseError;
+use crate::solution::{Answer, Solution};
 
-pub fn part_1(input_file: &str) {
-    let file = File::open(input_file).expect("file not found");
-    let lines: Result<Vec<_>, _> = io::BufReader::new(file).lines().collect();
-    let lines = lines.expect("Error reading lines");
-    let result = find_xmas(lines);
-    println!("XMAS appears {} times", result);
-}
+pub struct Day4;
 
-pub fn part_2(input_file: &str) {
-    let file = File::open(input_file).expect("file not found");
-    let lines: Result<Vec<_>, _> = io::BufReader::new(file).lines().collect();
-    let lines = lines.expect("Error reading lines");
-    let result = find_cross_mas(lines);
-    println!("XMAS appears {} times", result);
-}
+impl Solution for Day4 {
+    type Input = Grid<char>;
 
-fn find_xmas(lines: Vec<String>) -> usize {
-    let offsets = dbg!(vec![-1, 0, 1]
-        .into_iter()
-        .cartesian_product(vec![-1, 0, 1].into_iter())
-        .filter(|offset| offset.0 != 0 || offset.1 != 0)
-        .collect());
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse_chars(input)
+    }
 
-    let mut count = 0;
+    fn part_1(grid: &Self::Input) -> Answer {
+        find_xmas(grid).into()
+    }
 
-    for (row, line) in lines.iter().enumerate() {
-        for (column, char) in line.chars().enumerate() {
-            if char == 'X' {
-                count += count_word_occurrences((row as i32, column as i32), &lines, &offsets, "MAS");
-            }
-        }
+    fn part_2(grid: &Self::Input) -> Answer {
+        find_cross_mas(grid).into()
     }
+}
 
-    count
+fn find_xmas(grid: &Grid<char>) -> usize {
+    grid.iter()
+        .filter(|(_, char)| **char == 'X')
+        .map(|(point, _)| count_word_occurrences(&point, grid, &Point::ALL_OFFSETS, "MAS"))
+        .sum()
 }
 
-fn count_word_occurrences(position: (i32, i32), lines: &Vec<String>, offsets: &Vec<(i32, i32)>, searching_for: &str) -> usize {
+fn count_word_occurrences(position: &Point, grid: &Grid<char>, offsets: &[Point], searching_for: &str) -> usize {
     if searching_for.is_empty() {
         return 1;
     }
 
     let mut count = 0;
     for offset in offsets {
-        let new_position = (position.0 as i32 + offset.0, position.1 as i32 + offset.1);
-        if new_position.0 < 0
-            || new_position.0 >= lines.len() as i32
-            || new_position.1 < 0
-            || new_position.1 >= lines[new_position.0 as usize].len() as i32 {
-            // new position is outside of the bounds of the word grid
-            continue;
-        }
-
-        if lines[new_position.0 as usize].chars().nth(new_position.1 as usize) == searching_for.chars().nth(0) {
-            count += count_word_occurrences(new_position, lines, &vec![offset.clone()], &searching_for[1..]);
+        let new_position = position.add(offset);
+        // `get` is None if the new position is outside of the bounds of the word grid
+        if grid.get(&new_position).copied() == searching_for.chars().next() {
+            count += count_word_occurrences(&new_position, grid, slice::from_ref(offset), &searching_for[1..]);
         }
     }
     count
 }
 
-fn find_cross_mas(lines: Vec<String>) -> usize {
-    let offsets = vec![
-        (1, 1),
-        (-1, 1),
-        (1, -1),
-        (-1, -1),
+fn find_cross_mas(grid: &Grid<char>) -> usize {
+    let offsets = [
+        Point::new(1, 1),
+        Point::new(-1, 1),
+        Point::new(1, -1),
+        Point::new(-1, -1),
     ];
 
-    let mut count = 0;
-
-    for (row, line) in lines.iter().enumerate() {
-        for (column, char) in line.chars().enumerate() {
-            if char == 'A' {
-                let num_mas_found = offsets.iter()
-                    .map(|offset| {
-                        let position = (row as i32 - offset.0 * 2, column as i32 - offset.1 * 2);
-                        count_word_occurrences(position, &lines, &vec![offset.clone()], "MAS")
-                    })
-                    .sum::<usize>();
-                if num_mas_found == 2 {
-                    count += 1;
-                }
-            }
-        }
-    }
-
-    count
+    grid.iter()
+        .filter(|(_, char)| **char == 'A')
+        .filter(|(point, _)| {
+            let num_mas_found = offsets.iter()
+                .map(|offset| {
+                    let position = point.subtract(&offset.scale(2));
+                    count_word_occurrences(&position, grid, slice::from_ref(offset), "MAS")
+                })
+                .sum::<usize>();
+            num_mas_found == 2
+        })
+        .count()
 }
 
 #[cfg(test)]
 mod tests {
+    use std::fs::read_to_string;
     use super::*;
 
     #[test]
     fn test_part_1_example() {
-        let file = File::open("inputs/day-4-example.txt").expect("file not found");
-        let lines: Result<Vec<_>, _> = io::BufReader::new(file).lines().collect();
-        let lines = lines.expect("Error reading lines");
-        let result = find_xmas(lines);
+        let grid = Day4::parse(&read_to_string("inputs/day-4-example.txt").unwrap()).unwrap();
+        let result = find_xmas(&grid);
         assert_eq!(result, 18);
     }
 
     #[test]
     fn test_part_1() {
-        let file = File::open("inputs/day-4-input.txt").expect("file not found");
-        let lines: Result<Vec<_>, _> = io::BufReader::new(file).lines().collect();
-        let lines = lines.expect("Error reading lines");
-        let result = find_xmas(lines);
+        let grid = Day4::parse(&read_to_string("inputs/day-4-input.txt").unwrap()).unwrap();
+        let result = find_xmas(&grid);
         assert_eq!(result, 2390);
     }
 
     #[test]
     fn test_part_2_example() {
-        let file = File::open("inputs/day-4-example.txt").expect("file not found");
-        let lines: Result<Vec<_>, _> = io::BufReader::new(file).lines().collect();
-        let lines = lines.expect("Error reading lines");
-        let result = find_cross_mas(lines);
+        let grid = Day4::parse(&read_to_string("inputs/day-4-example.txt").unwrap()).unwrap();
+        let result = find_cross_mas(&grid);
         assert_eq!(result, 9);
     }
 }
@@ -1,39 +1,39 @@
 use std::collections::HashMap;
 use itertools::Itertools;
+use crate::parse_error::ParseError;
+use crate::solution::{Answer, Solution};
 
-pub fn part_1(input_file: &str) {
-    let (ordering_rules, page_updates) = parse_file(input_file);
-    let result = page_updates.iter()
-        .filter(|&update| is_correctly_ordered(update, &ordering_rules))
-        .map(|update| {
-            if update.len() % 2 == 0 {
-                panic!("Update has an even number of pages!")
-            }
-            update[update.len() / 2]
-        })
-        .sum::<u64>();
-    println!("Part 1: {}", result);
-}
+pub struct Day5;
 
-pub fn part_2(input_file: &str) {
-    let (ordering_rules, page_updates) = parse_file(input_file);
-    let badly_ordered_updates = page_updates.iter()
-        .filter(|&update| !is_correctly_ordered(update, &ordering_rules))
-        .collect_vec();
-    let result = badly_ordered_updates.into_iter()
-        .map(|update| reorder_update(update, &ordering_rules))
-        .map(|update| {
-            if update.len() % 2 == 0 {
-                panic!("Update has an even number of pages!")
-            }
-            update[update.len() / 2]
-        })
-        .sum::<u64>();
-    println!("Part 2: {}", result);
+impl Solution for Day5 {
+    type Input = (Rules, Vec<Vec<u64>>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1((ordering_rules, page_updates): &Self::Input) -> Answer {
+        page_updates.iter()
+            .filter(|&update| is_correctly_ordered(update, ordering_rules))
+            .map(|update| update[update.len() / 2])
+            .sum::<u64>().into()
+    }
+
+    fn part_2((ordering_rules, page_updates): &Self::Input) -> Answer {
+        let badly_ordered_updates = page_updates.iter()
+            .filter(|&update| !is_correctly_ordered(update, ordering_rules))
+            .collect_vec();
+        badly_ordered_updates.into_iter()
+            .map(|update| reorder_update(update, ordering_rules))
+            .map(|update| update[update.len() / 2])
+            .sum::<u64>().into()
+    }
 }
 
+pub type Rules = HashMap<u64, OrderingRules>;
+
 #[derive(Debug)]
-struct OrderingRules {
+pub struct OrderingRules {
     must_come_before: Vec<u64>,
     must_come_after: Vec<u64>,
 }
@@ -47,15 +47,24 @@ impl OrderingRules {
     }
 }
 
-fn parse_file(file_name: &str) -> (HashMap<u64, OrderingRules>, Vec<Vec<u64>>) {
-    let full_input = std::fs::read_to_string(&file_name)
-        .expect("Failed to read file");
-    let (rules_part, updates_part) = full_input.split_once("\n\n")
-        .expect("File not formatted as expected");
+fn parse_input(input: &str) -> Result<(Rules, Vec<Vec<u64>>), ParseError> {
+    let (rules_part, updates_part) = input.split_once("\n\n")
+        .ok_or_else(|| ParseError::new(
+            "Expected a blank line between the ordering rules and the updates",
+            input,
+            &input[input.len()..]
+        ))?;
+    let parse_page = |page: &str| page.parse::<u64>()
+        .map_err(|_| ParseError::new("Expected a page number", input, page));
 
     let rules = rules_part.lines()
-        .map(|line| line.split_once("|").expect("Rules line not formatted as expected"))
-        .map(|(left, right)| (left.parse().unwrap(), right.parse().unwrap()))
+        .map(|line| {
+            let (left, right) = line.split_once("|")
+                .ok_or_else(|| ParseError::new("Expected a rule like 47|53", input, line))?;
+            Ok((parse_page(left)?, parse_page(right)?))
+        })
+        .collect::<Result<Vec<_>, ParseError>>()?
+        .into_iter()
         .fold(
             HashMap::new(),
             |mut rules, (left, right)| {
@@ -68,16 +77,21 @@ fn parse_file(file_name: &str) -> (HashMap<u64, OrderingRules>, Vec<Vec<u64>>) {
         );
 
     let updates = updates_part.lines()
-        .map(|line|
-            line.split(",")
-                .map(|num| num.parse().unwrap())
-                .collect_vec()
-        )
-        .collect();
-    (rules, updates)
+        .map(|line| {
+            let update = line.split(",")
+                .map(parse_page)
+                .collect::<Result<Vec<_>, ParseError>>()?;
+            // The middle page is the answer, so an update needs an odd number of pages to have one
+            if update.len() % 2 == 0 {
+                return Err(ParseError::new("Expected an update with an odd number of pages", input, line));
+            }
+            Ok(update)
+        })
+        .collect::<Result<_, ParseError>>()?;
+    Ok((rules, updates))
 }
 
-fn is_correctly_ordered(update: &Vec<u64>, rules: &HashMap<u64, OrderingRules>) -> bool {
+pub fn is_correctly_ordered(update: &[u64], rules: &Rules) -> bool {
     let update_indexes = update.iter()
         .enumerate()
         .map(|(index, num)| (*num, index))
@@ -98,11 +112,11 @@ fn is_correctly_ordered(update: &Vec<u64>, rules: &HashMap<u64, OrderingRules>)
                 .all(|other_index| *my_index > *other_index) {
                 return false;
             }
-            return true;
+            true
         })
 }
 
-fn reorder_update(update: &Vec<u64>, rules: &HashMap<u64, OrderingRules>) -> Vec<u64> {
+pub fn reorder_update(update: &[u64], rules: &Rules) -> Vec<u64> {
     let default_rules = OrderingRules::new();
     let mut result = Vec::new();
     for item in update {
@@ -125,6 +139,7 @@ fn reorder_update(update: &Vec<u64>, rules: &HashMap<u64, OrderingRules>) -> Vec
 
 #[cfg(test)]
 mod tests {
+    use std::fs::read_to_string;
     use rstest::rstest;
     use super::*;
 
@@ -136,24 +151,41 @@ mod tests {
     #[case(vec![61,13,29], false)]
     #[case(vec![97,13,75,29,47], false)]
     fn test_is_correctly_ordered(#[case] update: Vec<u64>, #[case] expected: bool) {
-        let (ordering_rules, _) = parse_file("inputs/day-5-example.txt");
+        let (ordering_rules, _) = parse_input(&read_to_string("inputs/day-5-example.txt").unwrap()).unwrap();
         assert_eq!(is_correctly_ordered(&update, &ordering_rules), expected)
     }
 
     #[test]
     fn parse_file_is_ok()
     {
-        let (ordering_rules, updates) = parse_file("inputs/day-5-example.txt");
+        let (ordering_rules, updates) = parse_input(&read_to_string("inputs/day-5-example.txt").unwrap()).unwrap();
         dbg!(ordering_rules);
         dbg!(updates);
     }
 
+    #[test]
+    fn parse_file_with_windows_line_endings() {
+        let input = "47|53\r\n97|13\r\n\r\n75,47,61\r\n";
+        let error = parse_input(input).unwrap_err();
+        assert_eq!(error.message, "Expected a blank line between the ordering rules and the updates");
+        assert!(error.report(input).contains("CRLF"));
+    }
+
+    #[rstest]
+    #[case("47|53\n\n75,47,61\n75,47\n", 4, 1, "75,47")]
+    #[case("47|53\n\n75,47,61\n75,47,61,53", 4, 1, "75,47,61,53")]
+    fn test_parsing_error(#[case] input: &str, #[case] line: usize, #[case] column: usize, #[case] text: &str) {
+        let error = parse_input(input).unwrap_err();
+        assert_eq!(error.message, "Expected an update with an odd number of pages");
+        assert_eq!((error.line, error.column, error.text.as_str()), (line, column, text));
+    }
+
     #[rstest]
     #[case(vec![75,97,47,61,53], vec![97,75,47,61,53])]
     #[case(vec![61,13,29], vec![61,29,13])]
     #[case(vec![97,13,75,29,47], vec![97,75,47,29,13])]
     fn test_reordering(#[case] update: Vec<u64>, #[case] expected: Vec<u64>) {
-        let (ordering_rules, _) = parse_file("inputs/day-5-example.txt");
+        let (ordering_rules, _) = parse_input(&read_to_string("inputs/day-5-example.txt").unwrap()).unwrap();
         assert_eq!(reorder_update(&update, &ordering_rules), expected)
     }
 
@@ -1,125 +1,81 @@
 use std::collections::{HashSet};
-use std::fs::File;
-use std::io;
-use std::io::BufRead;
-
-pub fn part_1(input_file: &str) {
-    let (map, position) = parse_file(input_file);
-    let positions: HashSet<Point> = traverse(&map, &position);
-    println!("Positions travelled: {}", positions.len());
-}
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use crate::grid::{Direction, Grid, Point};
+use crate::parallel;
+use crate::parse_error::ParseError;
+use crate::solution::{Answer, Solution};
 
-pub fn part_2(input_file: &str) {
-    let (map, position) = parse_file(input_file);
-    let positions: HashSet<Point> = find_loops(&map, &position);
-    println!("Positions we can put stuff to make a paradox: {}", positions.len());
-}
+pub struct Day6;
 
-#[derive(Debug, Eq, PartialEq, Hash, Clone)]
-struct Point {
-    x: i64,
-    y: i64,
-}
+impl Solution for Day6 {
+    type Input = (Map, Position);
 
-impl Point {
-    fn add(&self, other: &Point) -> Point {
-        Point {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        }
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
-}
-
-#[derive(Debug, Eq, PartialEq, Hash, Clone)]
-enum Direction {
-    Up, Down, Left, Right,
-}
 
-impl Direction {
-    fn vector(&self) -> Point {
-        match self {
-            Direction::Up => Point { x: 0, y: -1 },
-            Direction::Down => Point { x: 0, y: 1 },
-            Direction::Left => Point { x: -1, y: 0 },
-            Direction::Right => Point { x: 1, y: 0 },
-        }
+    fn part_1((map, position): &Self::Input) -> Answer {
+        let positions: HashSet<Point> = traverse(map, position);
+        positions.len().into()
     }
 
-    fn next(&self) -> Self {
-        match self {
-            Direction::Up => Direction::Right,
-            Direction::Right => Direction::Down,
-            Direction::Down => Direction::Left,
-            Direction::Left => Direction::Up,
-        }
+    fn part_2((map, position): &Self::Input) -> Answer {
+        let positions: HashSet<Point> = find_loops(map, position);
+        positions.len().into()
     }
 }
 
 #[derive(Debug, Eq, PartialEq, Hash, Clone)]
-struct Position {
+pub struct Position {
     point: Point,
     direction: Direction
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
-struct Map {
-    obstacles: HashSet<Point>,
-    width: usize,
-    height: usize,
+pub struct Map {
+    obstacles: Grid<bool>,
 }
 
 impl Map {
     fn contains(&self, point: &Point) -> bool {
-        point.x >= 0
-        && point.x < self.width as i64
-        && point.y >= 0
-        && point.y < self.height as i64
+        self.obstacles.contains(point)
     }
-}
 
-fn parse_file(file_name: &str) -> (Map, Position) {
-    let file = File::open(file_name).expect("file not found");
-    let lines: Result<Vec<_>, _> = io::BufReader::new(file).lines().collect();
-    let lines = lines.expect("Error reading lines");
-
-    let height = lines.len();
-    let width = lines[0].len();
+    fn is_obstacle(&self, point: &Point) -> bool {
+        self.obstacles.get(point) == Some(&true)
+    }
+}
 
-    let mut obstacles = HashSet::new();
-    let mut position = None;
+fn parse_input(input: &str) -> Result<(Map, Position), ParseError> {
+    let grid = Grid::parse(input, |c| match c {
+        '.' | '#' | '^' => Some(c),
+        _ => None,
+    })?;
 
-    for (y, line) in lines.into_iter().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            if c == '#' {
-                obstacles.insert(Point { x: x as i64, y: y as i64 });
-            }
-            if c == '^' {
-                position = Some(Position {
-                    point: Point { x: x as i64, y: y as i64 },
-                    direction: Direction::Up,
-                });
-            }
-        }
-    }
+    let position = grid.iter()
+        .find(|(_, c)| **c == '^')
+        .map(|(point, _)| Position {
+            point,
+            direction: Direction::Up,
+        })
+        .ok_or_else(|| ParseError::new("Expected a guard '^' on the map", input, &input[input.len()..]))?;
 
     let map = Map {
-        obstacles,
-        width,
-        height,
+        obstacles: grid.map(|c| *c == '#'),
     };
-
-    (map, position.unwrap())
+    Ok((map, position))
 }
 
-fn traverse(map: &Map, position: &Position) -> HashSet<Point> {
+pub fn traverse(map: &Map, position: &Position) -> HashSet<Point> {
     let mut result = HashSet::new();
     let mut current_position = position.clone();
     while map.contains(&current_position.point) {
         result.insert(current_position.point.clone());
 
         let next_point = current_position.point.add(&current_position.direction.vector());
-        if map.obstacles.contains(&next_point) {
-            current_position.direction = current_position.direction.next();
+        if map.is_obstacle(&next_point) {
+            current_position.direction = current_position.direction.turn_right();
         } else {
             current_position.point = next_point;
         }
@@ -127,28 +83,19 @@ fn traverse(map: &Map, position: &Position) -> HashSet<Point> {
     result
 }
 
-fn find_loops(map: &Map, starting_position: &Position) -> HashSet<Point> {
-    let mut result = HashSet::new();
-    let mut current_position = starting_position.clone();
-    while map.contains(&current_position.point) {
-
-        let next_point = current_position.point.add(&current_position.direction.vector());
-        if map.obstacles.contains(&next_point) {
-            current_position.direction = current_position.direction.next();
-            continue;
-        }
-
-        if map.contains(&next_point) && next_point != starting_position.point {
+// Only points on the guard's original route can change where it goes, and each is tried on its own
+pub fn find_loops(map: &Map, starting_position: &Position) -> HashSet<Point> {
+    let candidates = traverse(map, starting_position).into_iter()
+        .filter(|point| *point != starting_position.point)
+        .collect::<Vec<Point>>();
+    parallel::iter(&candidates)
+        .filter(|candidate| {
             let mut changed_map = map.clone();
-            changed_map.obstacles.insert(next_point.clone());
-            if loop_is_possible(changed_map, starting_position.clone()) {
-                result.insert(next_point.clone());
-            }
-        }
-
-        current_position.point = next_point;
-    }
-    result
+            *changed_map.obstacles.get_mut(candidate).unwrap() = true;
+            loop_is_possible(changed_map, starting_position.clone())
+        })
+        .cloned()
+        .collect()
 }
 
 fn loop_is_possible(map: Map, loop_check_starting_position: Position) -> bool {
@@ -163,22 +110,23 @@ fn loop_is_possible(map: Map, loop_check_starting_position: Position) -> bool {
         seen_positions.insert(current_position.clone());
 
         let next_point = current_position.point.add(&current_position.direction.vector());
-        if map.obstacles.contains(&next_point) {
-            current_position.direction = current_position.direction.next();
+        if map.is_obstacle(&next_point) {
+            current_position.direction = current_position.direction.turn_right();
         } else {
             current_position.point = next_point;
         }
     }
-    return false;
+    false
 }
 
 #[cfg(test)]
 mod tests {
+    use std::fs::read_to_string;
     use super::*;
 
     #[test]
     fn test_parse_file() {
-        let (map, position) = parse_file("inputs/day-6-example.txt");
+        let (map, position) = parse_input(&read_to_string("inputs/day-6-example.txt").unwrap()).unwrap();
         let expected_obstacles = vec![
             Point { x: 4, y: 0 },
             Point { x: 9, y: 1 },
@@ -188,8 +136,12 @@ mod tests {
             Point { x: 8, y: 7 },
             Point { x: 0, y: 8 },
             Point { x: 6, y: 9 },
-        ].into_iter().collect();
-        assert_eq!(map.obstacles, expected_obstacles);
+        ];
+        let obstacles = map.obstacles.iter()
+            .filter(|(_, obstacle)| **obstacle)
+            .map(|(point, _)| point)
+            .collect::<HashSet<_>>();
+        assert_eq!(obstacles, expected_obstacles.into_iter().collect());
         let expected_position = Position {
             point: Point {
                 x: 4,
@@ -202,11 +154,11 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        let (map, position) = parse_file("inputs/day-6-example.txt");
+        let (map, position) = parse_input(&read_to_string("inputs/day-6-example.txt").unwrap()).unwrap();
         let positions = traverse(&map, &position);
-        for y in 0..map.height {
-            for x in 0..map.width {
-                if map.obstacles.contains(&Point { x: x as i64, y: y as i64 }) {
+        for y in 0..map.obstacles.height() {
+            for x in 0..map.obstacles.width() {
+                if map.is_obstacle(&Point { x: x as i64, y: y as i64 }) {
                     print!("#");
                 } else if position.point.x == x as i64 && position.point.y == y as i64 {
                     print!("^");
@@ -223,7 +175,7 @@ mod tests {
 
     #[test]
     fn test_part_2() {
-        let (map, position) = parse_file("inputs/day-6-input.txt");
+        let (map, position) = parse_input(&read_to_string("inputs/day-6-example.txt").unwrap()).unwrap();
         let positions = find_loops(&map, &position);
         assert_eq!(positions.len(), 6);
     }
@@ -1,30 +1,36 @@
 use itertools::Itertools;
-use std::fs::File;
-use std::io;
-use std::io::BufRead;
-
-pub fn part_1(input_file: &str) {
-    let equations = parse_file(input_file);
-    let result = equations
-        .into_iter()
-        .filter(|equation| !equation.find_solutions(Operation::all_p1()).is_empty())
-        .map(|equation| equation.test_value)
-        .sum::<u64>();
-    println!("{}", result);
-}
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use crate::parallel;
+use crate::parse_error::ParseError;
+use crate::solution::{Answer, Solution};
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<Equation>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
 
-pub fn part_2(input_file: &str) {
-    let equations = parse_file(input_file);
-    let result = equations
-        .into_iter()
-        .filter(|equation| !equation.find_solutions(Operation::all_p2()).is_empty())
-        .map(|equation| equation.test_value)
-        .sum::<u64>();
-    println!("{}", result);
+    fn part_1(equations: &Self::Input) -> Answer {
+        parallel::iter(equations)
+            .filter(|equation| !equation.find_solutions(Operation::all_p1()).is_empty())
+            .map(|equation| equation.test_value)
+            .sum::<u64>().into()
+    }
+
+    fn part_2(equations: &Self::Input) -> Answer {
+        parallel::iter(equations)
+            .filter(|equation| !equation.find_solutions(Operation::all_p2()).is_empty())
+            .map(|equation| equation.test_value)
+            .sum::<u64>().into()
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
-struct Equation {
+pub struct Equation {
     test_value: u64,
     items: Vec<u64>,
 }
@@ -69,7 +75,7 @@ impl Equation {
             .collect()
     }
 
-    fn evaluate(&self, operations: &Vec<Operation>) -> u64 {
+    fn evaluate(&self, operations: &[Operation]) -> u64 {
         operations
             .iter()
             .enumerate()
@@ -79,27 +85,32 @@ impl Equation {
     }
 }
 
-fn parse_file(file_name: &str) -> Vec<Equation> {
-    let file = File::open(file_name).expect("file not found");
-    let lines: Result<Vec<_>, _> = io::BufReader::new(file).lines().collect();
-    let lines = lines.expect("Error reading lines");
-    lines
-        .into_iter()
-        .map(|line| parse_equation(&line))
+fn parse_input(input: &str) -> Result<Vec<Equation>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse_equation(line).map_err(|error| error.offset_by_lines(index)))
         .collect()
 }
 
-fn parse_equation(line: &str) -> Equation {
+fn parse_equation(line: &str) -> Result<Equation, ParseError> {
     let (left, right) = line
         .split_once(":")
-        .expect("Line not formatted as expected");
-    let test_value = left.parse::<u64>().expect("Unable to parse test value");
+        .ok_or_else(|| ParseError::new("Expected a test value followed by ':'", line, line))?;
+    let test_value = left
+        .parse::<u64>()
+        .map_err(|_| ParseError::new("Expected a test value", line, left))?;
     let items = right
-        .trim()
         .split_whitespace()
-        .map(|item| item.parse::<u64>().expect("Unable to parse item"))
-        .collect();
-    Equation { test_value, items }
+        .map(|item| {
+            item.parse::<u64>()
+                .map_err(|_| ParseError::new("Expected a number", line, item))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if items.is_empty() {
+        return Err(ParseError::new("Expected at least one number", line, &line[line.len()..]));
+    }
+    Ok(Equation { test_value, items })
 }
 
 #[cfg(test)]
@@ -118,7 +129,7 @@ mod tests {
     #[case("21037: 9 7 18 13", Equation{ test_value: 21037, items: vec![9, 7, 18, 13] })]
     #[case("292: 11 6 16 20", Equation{ test_value: 292, items: vec![11, 6, 16, 20] })]
     fn test_parse_line(#[case] line: &str, #[case] expected_result: Equation) {
-        assert_eq!(parse_equation(line), expected_result);
+        assert_eq!(parse_equation(line), Ok(expected_result));
     }
 
     #[rstest]
@@ -137,7 +148,7 @@ mod tests {
     #[case("21037: 9 7 18 13", vec![])]
     #[case("292: 11 6 16 20", vec![ vec![Operation::Add, Operation::Multiply, Operation::Add] ])]
     fn test_find_solution(#[case] line: &str, #[case] solutions: Vec<Vec<Operation>>) {
-        let equation = parse_equation(line);
+        let equation = parse_equation(line).unwrap();
         assert_eq!(equation.find_solutions(Operation::all_p2()), solutions);
     }
 
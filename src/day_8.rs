@@ -1,54 +1,39 @@
 use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io;
-use std::io::BufRead;
 use itertools::Itertools;
+use crate::grid::{Grid, Point};
+use crate::parse_error::ParseError;
+use crate::solution::{Answer, Solution};
 
-pub fn part_1(input_file: &str) {
-    let map = parse_file(input_file);
-    let antinodes = map.find_antinodes();
-    let result: HashSet<Point> = antinodes.into_iter()
-        .map(|antinode| antinode.position)
-        .filter(|position| map.contains(position))
-        .collect();
-    println!("Unique antinode positions: {}", result.len());
-}
+pub struct Day8;
 
-pub fn part_2(input_file: &str) {
-    let map = parse_file(input_file);
-    let antinodes = map.find_antinodes_part_2();
-    let result: HashSet<Point> = antinodes.into_iter()
-        .map(|antinode| antinode.position)
-        .collect();
-    println!("Unique antinode positions: {}", result.len());
-}
+impl Solution for Day8 {
+    type Input = Map;
 
-#[derive(Debug, Eq, PartialEq, Hash, Clone)]
-struct Point {
-    x: i64,
-    y: i64,
-}
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
 
-impl Point {
-    fn add(&self, other: &Point) -> Point {
-        Point {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        }
+    fn part_1(map: &Self::Input) -> Answer {
+        let antinodes = map.find_antinodes();
+        let result: HashSet<Point> = antinodes.into_iter()
+            .map(|antinode| antinode.position)
+            .filter(|position| map.contains(position))
+            .collect();
+        result.len().into()
     }
 
-    fn subtract(&self, other: &Point) -> Point {
-        Point {
-            x: self.x - other.x,
-            y: self.y - other.y,
-        }
+    fn part_2(map: &Self::Input) -> Answer {
+        let antinodes = map.find_antinodes_part_2();
+        let result: HashSet<Point> = antinodes.into_iter()
+            .map(|antinode| antinode.position)
+            .collect();
+        result.len().into()
     }
 }
 
-struct Map {
+pub struct Map {
     antennas: HashMap<char, Vec<Point>>,
-    width: i64,
-    height: i64,
+    grid: Grid<char>,
 }
 
 impl Map {
@@ -67,9 +52,7 @@ impl Map {
                     .collect_vec()
             })
             .collect_vec();
-        let antinodes = (0..self.width)
-            .cartesian_product(0..self.height)
-            .map(|(x, y)| Point { x, y })
+        self.grid.points()
             .filter_map(|point| {
                 let x = equations.iter().find(|(_f, equation)| equation.evaluate(&point));
                 if let Some((x, _)) = x {
@@ -78,25 +61,11 @@ impl Map {
                     None
                 }
             })
-            .collect_vec();
-        // for y in 0..self.height {
-        //     for x in 0..self.width {
-        //         if let Some(antinode) = antinodes.iter().find(|a| a.position == Point { x, y }) {
-        //             print!("{}", antinode.frequency);
-        //         } else {
-        //             print!(".");
-        //         }
-        //     }
-        //     println!();
-        // }
-        return antinodes;
+            .collect_vec()
     }
 
     fn contains(&self, point: &Point) -> bool {
-        point.x >= 0
-            && point.x < self.width as i64
-            && point.y >= 0
-            && point.y < self.height as i64
+        self.grid.contains(point)
     }
 }
 
@@ -125,11 +94,11 @@ impl LineEquation {
         let expected = point.x as f64 * self.m + self.b;
         // Floating point precision errors mean we can't just check if they're equal
         // I'll come back to this at some point and do it a different way (figuring out the step for each line)
-        return (point.y as f64 - expected).abs() < 0.01;
+        (point.y as f64 - expected).abs() < 0.01
     }
 }
 
-fn find_antinodes(frequency: char, antennas: &Vec<Point>) -> Vec<AntiNode> {
+fn find_antinodes(frequency: char, antennas: &[Point]) -> Vec<AntiNode> {
     antennas.iter()
         .tuple_combinations()
         .flat_map(|(p1, p2)| {
@@ -143,38 +112,29 @@ fn find_antinodes(frequency: char, antennas: &Vec<Point>) -> Vec<AntiNode> {
         .collect()
 }
 
-fn parse_file(file_name: &str) -> Map {
-    let file = File::open(file_name).expect("file not found");
-    let lines: Result<Vec<_>, _> = io::BufReader::new(file).lines().collect();
-    let lines = lines.expect("Error reading lines");
-    let width = lines[0].len() as i64;
-    let height = lines.len() as i64;
+fn parse_input(input: &str) -> Result<Map, ParseError> {
+    let grid = Grid::parse_chars(input)?;
     let mut antennas: HashMap<char, Vec<Point>> = HashMap::new();
-
-    for (y, line) in lines.into_iter().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            if c == '.' {
-                continue;
-            }
-            antennas.entry(c).or_insert(Vec::new())
-                .push(Point { x: x as i64, y: y as i64 });
+    for (point, c) in grid.iter() {
+        if *c != '.' {
+            antennas.entry(*c).or_default().push(point);
         }
     }
-    Map {
+    Ok(Map {
         antennas,
-        width,
-        height,
-    }
+        grid,
+    })
 }
 
 #[cfg(test)]
 mod tests {
+    use std::fs::read_to_string;
     use rstest::rstest;
     use super::*;
 
     #[test]
     fn test_parse_file() {
-        let map = parse_file("inputs/day-8-example.txt");
+        let map = parse_input(&read_to_string("inputs/day-8-example.txt").unwrap()).unwrap();
         assert_eq!(map.antennas.len(), 2);
         assert_eq!(map.antennas[&'0'], vec![
             Point { x: 8, y: 1 },
@@ -191,18 +151,18 @@ mod tests {
 
     #[test]
     fn test_find_antinodes() {
-        let map = parse_file("inputs/day-8-example.txt");
+        let map = parse_input(&read_to_string("inputs/day-8-example.txt").unwrap()).unwrap();
         let result: HashSet<Point> = map.find_antinodes()
             .into_iter()
             .map(|antinode| antinode.position)
-            .filter(|position| map.contains(&position))
+            .filter(|position| map.contains(position))
             .collect();
         assert_eq!(result.len(), 14);
     }
 
     #[test]
     fn test_find_antinodes_part_2() {
-        let map = parse_file("inputs/day-8-example.txt");
+        let map = parse_input(&read_to_string("inputs/day-8-example.txt").unwrap()).unwrap();
         let result: HashSet<Point> = map.find_antinodes_part_2()
             .into_iter()
             .map(|antinode| antinode.position)
@@ -1,30 +1,44 @@
 
 use std::ops::Range;
 use itertools::Itertools;
+use crate::parse_error::ParseError;
+use crate::solution::{Answer, Solution};
 
-pub fn part_1(input_file: &str) {
-    let filesystem = parse_file(input_file);
-    let compacted_filesystem = filesystem.compact();
-    println!("Checksum: {}", compacted_filesystem.checksum());
-}
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = FileSystem;
 
-pub fn part_2(input_file: &str) {
-    let filesystem = parse_file(input_file);
-    let compacted_filesystem = filesystem.compact_v2();
-    println!("Checksum: {}", compacted_filesystem.checksum());
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(filesystem: &Self::Input) -> Answer {
+        let compacted_filesystem = filesystem.compact();
+        compacted_filesystem.checksum().into()
+    }
+
+    fn part_2(filesystem: &Self::Input) -> Answer {
+        let compacted_filesystem = filesystem.compact_v2();
+        compacted_filesystem.checksum().into()
+    }
 }
 
-fn parse_file(input_file: &str) -> FileSystem {
-    let line = std::fs::read_to_string(input_file)
-        .expect("Unable to read file");
+fn parse_input(input: &str) -> Result<FileSystem, ParseError> {
     let mut next_file_id = 0;
     let mut pointer = 0;
     let mut next_block_is_empty = false;
     let mut filesystem = FileSystem {
         blocks: Vec::new(),
     };
-    for c in line.chars() {
-        let length = c.to_digit(10).expect("Unexpected character") as usize;
+    let disk_map = input.trim_end();
+    if disk_map.is_empty() {
+        return Err(ParseError::new("Expected a disk map", input, disk_map));
+    }
+    for (index, c) in disk_map.char_indices() {
+        let length = c.to_digit(10)
+            .ok_or_else(|| ParseError::new("Expected a digit", input, &disk_map[index..index + c.len_utf8()]))?
+            as usize;
         if length == 0 {
             if !next_block_is_empty {
                 next_file_id += 1;
@@ -47,11 +61,11 @@ fn parse_file(input_file: &str) -> FileSystem {
         next_block_is_empty = !next_block_is_empty;
         pointer += length;
     }
-    filesystem
+    Ok(filesystem)
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
-struct FileSystem {
+pub struct FileSystem {
     blocks: Vec<ContiguousFileBlocks>
 }
 
@@ -62,7 +76,7 @@ struct ContiguousFileBlocks {
 }
 
 impl FileSystem {
-    fn compact(&self) -> FileSystem {
+    pub fn compact(&self) -> FileSystem {
         let mut compacted = self.clone();
 
         let mut pointer = 0;
@@ -77,9 +91,15 @@ impl FileSystem {
 
             let empty_block = compacted.blocks.remove(pointer);
 
-            let mut replacement_block = compacted.blocks.pop().unwrap();
+            // A disk map that starts with an empty file can leave nothing but free space to pop
+            let Some(mut replacement_block) = compacted.blocks.pop() else {
+                return compacted;
+            };
             while replacement_block.file_id.is_none() {
-                replacement_block = compacted.blocks.pop().unwrap();
+                let Some(block) = compacted.blocks.pop() else {
+                    return compacted;
+                };
+                replacement_block = block;
             }
 
             if pointer > compacted.blocks.len() {
@@ -119,7 +139,7 @@ impl FileSystem {
         compacted
     }
 
-    fn checksum(&self) -> u64 {
+    pub fn checksum(&self) -> u64 {
         self.blocks.iter()
             .map(|block| {
                 let positions_sum = (block.range.start + block.range.end - 1) as u64 * block.range.len() as u64 / 2;
@@ -128,18 +148,21 @@ impl FileSystem {
             .sum()
     }
 
-    fn compact_v2(&self) -> FileSystem {
+    pub fn compact_v2(&self) -> FileSystem {
         let mut compacted = self.clone();
 
-        let max_file_id = compacted.blocks.iter()
+        let Some(max_file_id) = compacted.blocks.iter()
             .filter_map(|block| block.file_id)
-            .last()
-            .expect("There should be a file");
+            .next_back() else {
+            return compacted;
+        };
 
         for file_id in (0..(max_file_id + 1)).rev() {
-            let (initial_position, block) = compacted.blocks.iter()
-                .find_position(|block| block.file_id == Some(file_id))
-                .expect("Couldn't find file");
+            // Files of length zero never got a block, so there's nothing to move
+            let Some((initial_position, block)) = compacted.blocks.iter()
+                .find_position(|block| block.file_id == Some(file_id)) else {
+                continue;
+            };
             let block_length = block.range.len();
             let first_empty_space = compacted.blocks.iter()
                 .take(initial_position)
@@ -183,6 +206,9 @@ impl FileSystem {
 
 #[cfg(test)]
 mod tests {
+    use std::fs::read_to_string;
+    use proptest::prelude::*;
+    use rstest::rstest;
     use super::*;
 
     #[test]
@@ -213,13 +239,22 @@ mod tests {
                 ContiguousFileBlocks { file_id: Some(9), range: 40..42 },
             ]
         };
-        assert_eq!(parse_file(input_file), expected_filesystem);
+        assert_eq!(parse_input(&read_to_string(input_file).unwrap()).unwrap(), expected_filesystem);
+    }
+
+    #[rstest]
+    #[case("12345x", 1, 6, "x")]
+    #[case("12\r\n345", 1, 3, "\r")]
+    #[case("", 1, 1, "")]
+    fn test_parse_file_error(#[case] input: &str, #[case] line: usize, #[case] column: usize, #[case] text: &str) {
+        let error = parse_input(input).unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (line, column, text));
     }
 
     #[test]
     fn test_compact() {
         let input_file = "inputs/day-9-example.txt";
-        let filesystem = parse_file(input_file);
+        let filesystem = parse_input(&read_to_string(input_file).unwrap()).unwrap();
         let expected_filesystem = FileSystem {
             blocks: vec![
                 ContiguousFileBlocks { file_id: Some(0), range: 0..2 },
@@ -244,15 +279,25 @@ mod tests {
     #[test]
     fn test_checksum() {
         let input_file = "inputs/day-9-example.txt";
-        let filesystem = parse_file(input_file);
+        let filesystem = parse_input(&read_to_string(input_file).unwrap()).unwrap();
         let compacted_filesystem = filesystem.compact();
         assert_eq!(compacted_filesystem.checksum(), 1928);
     }
 
+    #[rstest]
+    #[case("0")]
+    #[case("05")]
+    #[case("050")]
+    fn test_compact_without_files(#[case] disk_map: &str) {
+        let filesystem = parse_input(disk_map).unwrap();
+        assert_eq!(filesystem.compact().checksum(), 0);
+        assert_eq!(filesystem.compact_v2().checksum(), 0);
+    }
+
     #[test]
     fn test_compact_v2() {
         let input_file = "inputs/day-9-example.txt";
-        let filesystem = parse_file(input_file);
+        let filesystem = parse_input(&read_to_string(input_file).unwrap()).unwrap();
         let expected_filesystem = FileSystem {
             blocks: vec![
                 ContiguousFileBlocks { file_id: Some(0), range: 0..2 },
@@ -283,8 +328,57 @@ mod tests {
     #[test]
     fn test_checksum_v2() {
         let input_file = "inputs/day-9-example.txt";
-        let filesystem = parse_file(input_file);
+        let filesystem = parse_input(&read_to_string(input_file).unwrap()).unwrap();
         let compacted_filesystem = filesystem.compact_v2();
         assert_eq!(compacted_filesystem.checksum(), 2858);
     }
-}
\ No newline at end of file
+
+    // One entry per block on the disk, holding the ID of the file stored there
+    fn expand(filesystem: &FileSystem) -> Vec<Option<u64>> {
+        let mut disk = Vec::new();
+        for block in &filesystem.blocks {
+            disk.resize(block.range.start, None);
+            disk.extend(block.range.clone().map(|_| block.file_id));
+        }
+        while disk.last() == Some(&None) {
+            disk.pop();
+        }
+        disk
+    }
+
+    fn expanded_checksum(disk: &[Option<u64>]) -> u64 {
+        disk.iter()
+            .enumerate()
+            .map(|(position, file_id)| position as u64 * file_id.unwrap_or(0))
+            .sum()
+    }
+
+    // Part 1 done the slow way, moving one block at a time from the end into the first gap
+    fn compact_block_by_block(mut disk: Vec<Option<u64>>) -> Vec<Option<u64>> {
+        let mut gap = 0;
+        while let Some(offset) = disk[gap..].iter().position(Option::is_none) {
+            gap += offset;
+            disk.swap_remove(gap);
+            while disk.last() == Some(&None) {
+                disk.pop();
+            }
+        }
+        disk
+    }
+
+    proptest! {
+        #[test]
+        fn test_checksum_matches_expanded_blocks(disk_map in "[0-9]{1,21}") {
+            let filesystem = parse_input(&disk_map).unwrap();
+            for filesystem in [filesystem.clone(), filesystem.compact(), filesystem.compact_v2()] {
+                prop_assert_eq!(filesystem.checksum(), expanded_checksum(&expand(&filesystem)));
+            }
+        }
+
+        #[test]
+        fn test_compact_matches_block_by_block(disk_map in "[0-9]{1,21}") {
+            let filesystem = parse_input(&disk_map).unwrap();
+            prop_assert_eq!(expand(&filesystem.compact()), compact_block_by_block(expand(&filesystem)));
+        }
+    }
+}
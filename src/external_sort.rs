@@ -0,0 +1,157 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs::File;
+use std::io;
+use std::io::{BufReader, BufWriter, Read, Write};
+use tempfile::{NamedTempFile, TempPath};
+
+// The most runs merged at once, which bounds the files open during a merge
+pub const FAN_IN: usize = 64;
+
+// Sorts more values than fit in memory. Values are buffered up to `chunk_size` at a time, each full
+// buffer is sorted and written to a temporary file as a run, and `merged` reads the runs back
+// together in order. Only the paths are kept between merges, so no files stay open, and the files
+// are deleted when the sort is dropped
+pub struct ExternalSort {
+    chunk_size: usize,
+    buffer: Vec<u32>,
+    runs: Vec<TempPath>,
+}
+
+impl ExternalSort {
+    pub fn new(chunk_size: usize) -> Self {
+        ExternalSort {
+            chunk_size: chunk_size.max(1),
+            buffer: Vec::new(),
+            runs: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, value: u32) -> io::Result<()> {
+        self.buffer.push(value);
+        if self.buffer.len() >= self.chunk_size {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    pub fn runs(&self) -> usize {
+        self.runs.len()
+    }
+
+    // Every value pushed so far, in ascending order. Each call starts again from the smallest.
+    // While there are more than `FAN_IN` runs, groups of them are first merged into longer runs
+    pub fn merged(&mut self) -> io::Result<Merged> {
+        self.spill()?;
+        while self.runs.len() > FAN_IN {
+            let runs = std::mem::take(&mut self.runs);
+            for group in runs.chunks(FAN_IN) {
+                let run = write_run(merge(group)?)?;
+                self.runs.push(run);
+            }
+        }
+        merge(&self.runs)
+    }
+
+    fn spill(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        self.buffer.sort_unstable();
+        let run = write_run(self.buffer.iter().map(|value| Ok(*value)))?;
+        self.runs.push(run);
+        self.buffer.clear();
+        Ok(())
+    }
+}
+
+fn write_run(values: impl Iterator<Item = io::Result<u32>>) -> io::Result<TempPath> {
+    let mut file = NamedTempFile::new()?;
+    let mut writer = BufWriter::new(file.as_file_mut());
+    for value in values {
+        writer.write_all(&value?.to_le_bytes())?;
+    }
+    writer.flush()?;
+    drop(writer);
+    Ok(file.into_temp_path())
+}
+
+fn merge(runs: &[TempPath]) -> io::Result<Merged> {
+    let mut readers = Vec::new();
+    let mut heap = BinaryHeap::new();
+    for (run, path) in runs.iter().enumerate() {
+        let mut reader = BufReader::new(File::open(path)?);
+        if let Some(value) = read_value(&mut reader)? {
+            heap.push(Reverse((value, run)));
+        }
+        readers.push(reader);
+    }
+    Ok(Merged { readers, heap })
+}
+
+// A k-way merge of the sorted runs, holding only the next value from each run in memory
+pub struct Merged {
+    readers: Vec<BufReader<File>>,
+    heap: BinaryHeap<Reverse<(u32, usize)>>,
+}
+
+impl Iterator for Merged {
+    type Item = io::Result<u32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((value, run)) = self.heap.pop()?;
+        match read_value(&mut self.readers[run]) {
+            Ok(Some(next)) => self.heap.push(Reverse((next, run))),
+            Ok(None) => {}
+            Err(error) => return Some(Err(error)),
+        }
+        Some(Ok(value))
+    }
+}
+
+fn read_value(reader: &mut impl Read) -> io::Result<Option<u32>> {
+    let mut bytes = [0; 4];
+    match reader.read_exact(&mut bytes) {
+        Ok(()) => Ok(Some(u32::from_le_bytes(bytes))),
+        Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use super::*;
+
+    #[rstest]
+    #[case(vec![5, 3, 9, 1, 3, 7, 2], 3, 3)]
+    #[case(vec![5, 3, 9, 1, 3, 7, 2], 100, 1)]
+    #[case(vec![], 2, 0)]
+    fn test_merged(#[case] values: Vec<u32>, #[case] chunk_size: usize, #[case] runs: usize) {
+        let mut sort = ExternalSort::new(chunk_size);
+        for value in &values {
+            sort.push(*value).unwrap();
+        }
+        let mut expected = values.clone();
+        expected.sort();
+        assert_eq!(sort.merged().unwrap().collect::<io::Result<Vec<_>>>().unwrap(), expected);
+        assert_eq!(sort.runs(), runs);
+        // A second merge starts from the beginning again
+        assert_eq!(sort.merged().unwrap().collect::<io::Result<Vec<_>>>().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_merged_with_more_runs_than_fan_in() {
+        let values = (0..FAN_IN as u32 * 3 + 5).map(|value| (value * 7919) % 1000).collect::<Vec<_>>();
+        let mut sort = ExternalSort::new(1);
+        for value in &values {
+            sort.push(*value).unwrap();
+        }
+        assert_eq!(sort.runs(), values.len());
+        let mut expected = values.clone();
+        expected.sort();
+        assert_eq!(sort.merged().unwrap().collect::<io::Result<Vec<_>>>().unwrap(), expected);
+        assert!(sort.runs() <= FAN_IN, "{} runs", sort.runs());
+        assert_eq!(sort.merged().unwrap().collect::<io::Result<Vec<_>>>().unwrap(), expected);
+    }
+}
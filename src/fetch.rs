@@ -0,0 +1,190 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+use crate::input::example_files_in;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const SESSION_VARIABLE: &str = "AOC_SESSION";
+pub const YEAR: u16 = 2024;
+pub const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+// Downloads puzzle inputs into a local cache directory, normally inputs/
+pub struct Fetcher {
+    base_url: String,
+    session: String,
+    inputs_dir: PathBuf,
+}
+
+#[derive(Debug)]
+pub struct Fetched {
+    pub input: PathBuf,
+    pub example: Option<PathBuf>,
+    pub metadata: PathBuf,
+}
+
+// Written next to the input as day-N-fetch.toml
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct FetchMetadata {
+    pub url: String,
+    pub fetched_at: u64,
+}
+
+impl Fetcher {
+    pub fn new(base_url: &str, session: &str, inputs_dir: &Path) -> Self {
+        Fetcher {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            inputs_dir: inputs_dir.to_path_buf(),
+        }
+    }
+
+    // Fetches the input and, unless the day already has one, the first example on the puzzle page.
+    // Inputs never change once published, so a cached input is never downloaded again
+    pub fn fetch(&self, day: u8) -> Result<Fetched, String> {
+        let input_path = self.inputs_dir.join(format!("day-{}-input.txt", day));
+        if input_path.exists() {
+            return Err(format!("Day {} is already cached at {}", day, input_path.display()));
+        }
+
+        let input_url = format!("{}/{}/day/{}/input", self.base_url, YEAR, day);
+        let input = self.get(&input_url)?;
+        let puzzle = self.get(&format!("{}/{}/day/{}", self.base_url, YEAR, day))?;
+
+        fs::create_dir_all(&self.inputs_dir)
+            .map_err(|error| format!("Unable to create {}: {}", self.inputs_dir.display(), error))?;
+        write(&input_path, &input)?;
+
+        let example = match extract_example(&puzzle) {
+            Some(example) if example_files_in(&self.inputs_dir, day).is_empty() => {
+                let example_path = self.inputs_dir.join(format!("day-{}-example.txt", day));
+                write(&example_path, &example)?;
+                Some(example_path)
+            }
+            _ => None,
+        };
+
+        let metadata = FetchMetadata {
+            url: input_url,
+            fetched_at: SystemTime::now().duration_since(UNIX_EPOCH)
+                .map(|since_epoch| since_epoch.as_secs())
+                .unwrap_or(0),
+        };
+        let metadata_path = self.inputs_dir.join(format!("day-{}-fetch.toml", day));
+        let metadata = toml::to_string(&metadata).map_err(|error| error.to_string())?;
+        write(&metadata_path, &metadata)?;
+
+        Ok(Fetched {
+            input: input_path,
+            example,
+            metadata: metadata_path,
+        })
+    }
+
+    fn get(&self, url: &str) -> Result<String, String> {
+        ureq::get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .call()
+            .map_err(|error| format!("Fetch failed: {}", error))?
+            .into_string()
+            .map_err(|error| format!("Unable to read the response from {}: {}", url, error))
+    }
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|error| format!("Unable to write {}: {}", path.display(), error))
+}
+
+// The first <pre><code> block on a puzzle page is almost always the example input
+fn extract_example(page: &str) -> Option<String> {
+    let start = page.find("<pre><code>")? + "<pre><code>".len();
+    let length = page[start..].find("</code></pre>")?;
+    let mut example = String::new();
+    let mut in_tag = false;
+    for c in page[start..start + length].chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => example.push(c),
+            _ => {}
+        }
+    }
+    Some(example
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stub_server::serve;
+    use super::*;
+
+    const PUZZLE_PAGE: &str = "<main><p>For example:</p>\n<pre><code>3   4\n<em>4</em>   3\n</code></pre>\n<pre><code>other</code></pre></main>";
+
+    #[test]
+    fn test_fetch() {
+        let (base_url, server) = serve(vec![
+            ("/2024/day/1/input", 200, "1   2\n"),
+            ("/2024/day/1", 200, PUZZLE_PAGE),
+        ]);
+        let dir = tempfile::tempdir().unwrap();
+        let fetched = Fetcher::new(&base_url, "secret", dir.path()).fetch(1).unwrap();
+
+        assert_eq!(fs::read_to_string(&fetched.input).unwrap(), "1   2\n");
+        assert_eq!(fetched.example, Some(dir.path().join("day-1-example.txt")));
+        assert_eq!(fs::read_to_string(fetched.example.unwrap()).unwrap(), "3   4\n4   3\n");
+        let metadata: FetchMetadata = toml::from_str(&fs::read_to_string(&fetched.metadata).unwrap()).unwrap();
+        assert_eq!(metadata.url, format!("{}/2024/day/1/input", base_url));
+        assert!(metadata.fetched_at > 0);
+        let cookies = server.join().unwrap().into_iter()
+            .map(|request| request.cookie)
+            .collect::<Vec<_>>();
+        assert_eq!(cookies, vec![Some("session=secret".to_string()), Some("session=secret".to_string())]);
+    }
+
+    #[test]
+    fn test_fetch_keeps_existing_example() {
+        let (base_url, server) = serve(vec![
+            ("/2024/day/12/input", 200, "AB\n"),
+            ("/2024/day/12", 200, PUZZLE_PAGE),
+        ]);
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("day-12-example-1.txt"), "AA\n").unwrap();
+        let fetched = Fetcher::new(&base_url, "secret", dir.path()).fetch(12).unwrap();
+        assert_eq!(fetched.example, None);
+        assert_eq!(fs::read_to_string(dir.path().join("day-12-example-1.txt")).unwrap(), "AA\n");
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_fetch_refuses_cached_input() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("day-3-input.txt"), "cached").unwrap();
+        // Nothing listens here, so any request would fail with a different error
+        let error = Fetcher::new("http://127.0.0.1:9", "secret", dir.path()).fetch(3).unwrap_err();
+        assert!(error.contains("already cached"), "{}", error);
+    }
+
+    #[test]
+    fn test_fetch_error_status() {
+        let (base_url, server) = serve(vec![("/2024/day/5/input", 400, "Puzzle inputs differ by user.")]);
+        let dir = tempfile::tempdir().unwrap();
+        let error = Fetcher::new(&base_url, "expired", dir.path()).fetch(5).unwrap_err();
+        assert!(error.contains("400"), "{}", error);
+        assert!(!dir.path().join("day-5-input.txt").exists());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_extract_example() {
+        assert_eq!(extract_example(PUZZLE_PAGE), Some("3   4\n4   3\n".to_string()));
+        assert_eq!(extract_example("<pre><code>a &lt;b&gt; &amp;&amp; c</code></pre>"), Some("a <b> && c".to_string()));
+        assert_eq!(extract_example("<p>No examples today</p>"), None);
+    }
+}
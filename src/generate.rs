@@ -0,0 +1,341 @@
+use std::collections::{HashMap, HashSet};
+use itertools::Itertools;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use crate::grid::{Direction, Grid, Point};
+
+// ChaCha8 rather than rand's StdRng, whose algorithm may change between releases,
+// so that a seed always produces the same input
+type Random = ChaCha8Rng;
+
+type Generator = fn(&mut Random, usize) -> String;
+
+// Generates a random but valid input for a day. `size` is the number of lines, the width and
+// height of a grid, or the number of disk-map digits, stones or machines, whichever the
+// day's input is made of
+pub fn generate(day: u8, seed: u64, size: usize) -> Result<String, String> {
+    let generator = generator(day).ok_or_else(|| format!("No generator for day {}", day))?;
+    if size == 0 {
+        return Err("Size must be at least 1".to_string());
+    }
+    Ok(generator(&mut Random::seed_from_u64(seed), size))
+}
+
+// Days scaffolded with new-day have no generator until one is written here
+pub fn has_generator(day: u8) -> bool {
+    generator(day).is_some()
+}
+
+fn generator(day: u8) -> Option<Generator> {
+    let generator: Generator = match day {
+        1 => location_ids,
+        2 => level_reports,
+        3 => corrupted_memory,
+        4 => word_search,
+        5 => print_queue,
+        6 => guard_map,
+        7 => calibration_equations,
+        8 => antenna_map,
+        9 => disk_map,
+        10 => topographic_map,
+        11 => stones,
+        12 => garden_plots,
+        13 => claw_machines,
+        _ => return None,
+    };
+    Some(generator)
+}
+
+fn location_ids(random: &mut Random, size: usize) -> String {
+    let left = (0..size).map(|_| random.gen_range(10000..100000)).collect_vec();
+    // Some IDs appear in both lists, otherwise every similarity score would be 0
+    left.iter()
+        .map(|id| {
+            let right = if random.gen_bool(0.3) {
+                *left.choose(random).unwrap()
+            } else {
+                random.gen_range(10000..100000)
+            };
+            format!("{}   {}\n", id, right)
+        })
+        .collect()
+}
+
+fn level_reports(random: &mut Random, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let direction = if random.gen_bool(0.5) { 1 } else { -1 };
+            let mut level: i64 = random.gen_range(30..=70);
+            let mut report = (0..random.gen_range(5..=8))
+                .map(|_| {
+                    let current = level;
+                    level += direction * random.gen_range(1..=3);
+                    current
+                })
+                .collect_vec();
+            // Half the reports get one bad level, which the problem dampener may or may not cope with
+            if random.gen_bool(0.5) {
+                let index = random.gen_range(0..report.len());
+                report[index] += random.gen_range(-5..=5);
+            }
+            report.iter().join(" ") + "\n"
+        })
+        .collect()
+}
+
+fn corrupted_memory(random: &mut Random, size: usize) -> String {
+    const NOISE: [&str; 24] = [
+        "mul(", "mul", "do", "don't", "(", ")", ",", " ", "'", "?", "%", "[", "]", "{", "}", "<", ">",
+        "@", "#", "$", "!", "~", "*", "+",
+    ];
+    const NEAR_MISSES: [&str; 5] = ["how", "why", "from", "select", "mul "];
+    (0..size)
+        .map(|_| {
+            let mut line = (0..60)
+                .map(|_| match random.gen_range(0..10) {
+                    0..=2 => format!("mul({},{})", random.gen_range(1..1000), random.gen_range(1..1000)),
+                    3 => format!("{}({},{})", NEAR_MISSES.choose(random).unwrap(), random.gen_range(1..1000), random.gen_range(1..1000)),
+                    4 => ["do()", "don't()"].choose(random).unwrap().to_string(),
+                    _ => NOISE.choose(random).unwrap().to_string(),
+                })
+                .collect::<String>();
+            line.push('\n');
+            line
+        })
+        .collect()
+}
+
+fn word_search(random: &mut Random, size: usize) -> String {
+    grid(size, |_, _| *['X', 'M', 'A', 'S'].choose(random).unwrap())
+}
+
+fn print_queue(random: &mut Random, size: usize) -> String {
+    let mut pages = (10..100).collect_vec();
+    pages.shuffle(random);
+    pages.truncate(49);
+    let rank: HashMap<u64, usize> = pages.iter().enumerate().map(|(rank, page)| (*page, rank)).collect();
+
+    // Like the real inputs, every pair of pages has a rule, so every update has exactly one correct order
+    let mut rules = pages.iter()
+        .tuple_combinations()
+        .map(|(before, after)| format!("{}|{}\n", before, after))
+        .collect_vec();
+    rules.shuffle(random);
+
+    let updates = (0..size)
+        .map(|_| {
+            let length = random.gen_range(2..=11) * 2 + 1;
+            let mut update = pages.choose_multiple(random, length).collect_vec();
+            if random.gen_bool(0.5) {
+                update.sort_by_key(|page| rank[page]);
+            }
+            update.iter().join(",") + "\n"
+        })
+        .collect::<String>();
+    rules.concat() + "\n" + &updates
+}
+
+fn guard_map(random: &mut Random, size: usize) -> String {
+    loop {
+        let guard = (random.gen_range(0..size), random.gen_range(0..size));
+        let map = grid(size, |x, y| match (x, y) {
+            _ if (x, y) == guard => '^',
+            _ if random.gen_bool(0.05) => '#',
+            _ => '.',
+        });
+        let start = Point::new(guard.0 as i64, guard.1 as i64);
+        if guard_leaves(&Grid::parse_chars(&map).unwrap(), start) {
+            return map;
+        }
+    }
+}
+
+// The puzzle promises the guard walks off the map, so maps where they go round in circles are rejected
+fn guard_leaves(map: &Grid<char>, start: Point) -> bool {
+    let mut point = start;
+    let mut direction = Direction::Up;
+    let mut seen = HashSet::new();
+    while seen.insert((point.clone(), direction.clone())) {
+        let next = point.add(&direction.vector());
+        match map.get(&next) {
+            None => return true,
+            Some('#') => direction = direction.turn_right(),
+            Some(_) => point = next,
+        }
+    }
+    false
+}
+
+fn calibration_equations(random: &mut Random, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            // At most 8 two-digit items, so no combination of operators can overflow a u64
+            let items = (0..random.gen_range(2..=8)).map(|_| random.gen_range(1..100u64)).collect_vec();
+            // Half the equations are built from random operators, so they can be calibrated
+            let test_value = if random.gen_bool(0.5) {
+                items[1..].iter().fold(items[0], |value, item| match random.gen_range(0..3) {
+                    0 => value + item,
+                    1 => value * item,
+                    _ => format!("{}{}", value, item).parse().unwrap(),
+                })
+            } else {
+                random.gen_range(1..1_000_000_000)
+            };
+            format!("{}: {}\n", test_value, items.iter().join(" "))
+        })
+        .collect()
+}
+
+fn antenna_map(random: &mut Random, size: usize) -> String {
+    let frequencies = ('0'..='9').chain('a'..='z').chain('A'..='Z').collect_vec();
+    grid(size, |_, _| {
+        if random.gen_bool(0.03) {
+            *frequencies.choose(random).unwrap()
+        } else {
+            '.'
+        }
+    })
+}
+
+fn disk_map(random: &mut Random, size: usize) -> String {
+    let mut disk_map = (0..size)
+        .map(|index| {
+            let length = if index % 2 == 0 { random.gen_range(1..=9) } else { random.gen_range(0..=9) };
+            char::from_digit(length, 10).unwrap()
+        })
+        .collect::<String>();
+    disk_map.push('\n');
+    disk_map
+}
+
+// Heights fall away from scattered peaks, so there are long trails down to the 0s, with some
+// cells scrambled to break a few of them
+fn topographic_map(random: &mut Random, size: usize) -> String {
+    let peaks = (0..size * size / 50 + 1)
+        .map(|_| Point::new(random.gen_range(0..size as i64), random.gen_range(0..size as i64)))
+        .collect_vec();
+    grid(size, |x, y| {
+        let height = if random.gen_bool(0.1) {
+            random.gen_range(0..=9)
+        } else {
+            let distance = peaks.iter()
+                .map(|peak| (peak.x - x as i64).abs() + (peak.y - y as i64).abs())
+                .min()
+                .unwrap();
+            9 - distance.min(9) as u32
+        };
+        char::from_digit(height, 10).unwrap()
+    })
+}
+
+fn stones(random: &mut Random, size: usize) -> String {
+    let stones = (0..size)
+        .map(|_| if random.gen_bool(0.2) { random.gen_range(0..10) } else { random.gen_range(10..10_000_000) })
+        .join(" ");
+    stones + "\n"
+}
+
+// Each plot mostly copies the plant to its left or above, so the plants grow into irregular regions
+fn garden_plots(random: &mut Random, size: usize) -> String {
+    let mut plants: Vec<Vec<char>> = Vec::new();
+    for y in 0..size {
+        let mut row = Vec::new();
+        for x in 0..size {
+            let plant = match random.gen_range(0..10) {
+                0..=5 if x > 0 => row[x - 1],
+                6..=8 if y > 0 => plants[y - 1][x],
+                _ => random.gen_range('A'..='Z'),
+            };
+            row.push(plant);
+        }
+        plants.push(row);
+    }
+    plants.into_iter().map(|row| row.into_iter().collect::<String>() + "\n").collect()
+}
+
+fn claw_machines(random: &mut Random, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            // Real machines never have parallel buttons, which would make the presses ambiguous
+            let (a, b) = loop {
+                let a = (random.gen_range(10..100u64), random.gen_range(10..100u64));
+                let b = (random.gen_range(10..100u64), random.gen_range(10..100u64));
+                if a.0 * b.1 != a.1 * b.0 {
+                    break (a, b);
+                }
+            };
+            // Half the prizes can be won within 100 presses of each button
+            let prize = if random.gen_bool(0.5) {
+                let (a_presses, b_presses) = (random.gen_range(0..=100), random.gen_range(0..=100));
+                (a.0 * a_presses + b.0 * b_presses, a.1 * a_presses + b.1 * b_presses)
+            } else {
+                (random.gen_range(1000..20000), random.gen_range(1000..20000))
+            };
+            format!(
+                "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}\n",
+                a.0, a.1, b.0, b.1, prize.0, prize.1
+            )
+        })
+        .join("\n")
+}
+
+fn grid(size: usize, mut cell: impl FnMut(usize, usize) -> char) -> String {
+    (0..size)
+        .map(|y| (0..size).map(|x| cell(x, y)).collect::<String>() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use crate::solution::{find_day, DAYS};
+    use super::*;
+
+    #[test]
+    fn test_original_days_have_generators() {
+        for day in 1..=13 {
+            assert!(generate(day, 0, 1).is_ok(), "day {}", day);
+        }
+    }
+
+    #[rstest]
+    fn test_generated_inputs_solve(#[values(0, 1, 2)] seed: u64) {
+        for day in DAYS.iter().filter(|day| has_generator(day.number)) {
+            let input = generate(day.number, seed, 12).unwrap();
+            for part in [1, 2] {
+                let run = find_day(day.number).unwrap().run(&input, part);
+                assert!(matches!(run, Some(Ok(_))), "day {} part {} with seed {}:\n{}", day.number, part, seed, input);
+            }
+        }
+    }
+
+    #[test]
+    fn test_seed_is_reproducible() {
+        assert_eq!(generate(4, 7, 10), generate(4, 7, 10));
+        assert_ne!(generate(4, 7, 10), generate(4, 8, 10));
+    }
+
+    #[rstest]
+    #[case(1, 5, 5)]
+    #[case(4, 6, 6)]
+    #[case(11, 1, 1)]
+    #[case(13, 3, 11)]
+    fn test_size(#[case] day: u8, #[case] size: usize, #[case] lines: usize) {
+        assert_eq!(generate(day, 0, size).unwrap().lines().count(), lines);
+    }
+
+    #[test]
+    fn test_generate_errors() {
+        assert_eq!(generate(25, 0, 10), Err("No generator for day 25".to_string()));
+        assert_eq!(generate(1, 0, 0), Err("Size must be at least 1".to_string()));
+    }
+
+    #[test]
+    fn test_guard_leaves() {
+        let boxed_in = Grid::parse_chars(".#.\n#^#\n.#.\n").unwrap();
+        assert!(!guard_leaves(&boxed_in, Point::new(1, 1)));
+        let open = Grid::parse_chars("...\n.^.\n...\n").unwrap();
+        assert!(guard_leaves(&open, Point::new(1, 1)));
+    }
+}
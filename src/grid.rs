@@ -0,0 +1,255 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use crate::parse_error::ParseError;
+
+#[derive(Debug, Eq, PartialEq, Hash, Clone, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORTHOGONAL_OFFSETS: [Point; 4] = [
+        Point { x: -1, y: 0 },
+        Point { x: 1, y: 0 },
+        Point { x: 0, y: -1 },
+        Point { x: 0, y: 1 },
+    ];
+
+    pub const ALL_OFFSETS: [Point; 8] = [
+        Point { x: -1, y: -1 },
+        Point { x: 0, y: -1 },
+        Point { x: 1, y: -1 },
+        Point { x: -1, y: 0 },
+        Point { x: 1, y: 0 },
+        Point { x: -1, y: 1 },
+        Point { x: 0, y: 1 },
+        Point { x: 1, y: 1 },
+    ];
+
+    pub fn new(x: i64, y: i64) -> Point {
+        Point { x, y }
+    }
+
+    pub fn add(&self, other: &Point) -> Point {
+        Point {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+
+    pub fn subtract(&self, other: &Point) -> Point {
+        Point {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+
+    pub fn scale(&self, factor: i64) -> Point {
+        Point {
+            x: self.x * factor,
+            y: self.y * factor,
+        }
+    }
+
+    pub fn neighbours(&self) -> Vec<Point> {
+        Self::ORTHOGONAL_OFFSETS.iter()
+            .map(|diff| self.add(diff))
+            .collect()
+    }
+
+    pub fn neighbours_with_diagonals(&self) -> Vec<Point> {
+        Self::ALL_OFFSETS.iter()
+            .map(|diff| self.add(diff))
+            .collect()
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub enum Direction {
+    Up, Down, Left, Right,
+}
+
+impl Direction {
+    pub fn vector(&self) -> Point {
+        match self {
+            Direction::Up => Point { x: 0, y: -1 },
+            Direction::Down => Point { x: 0, y: 1 },
+            Direction::Left => Point { x: -1, y: 0 },
+            Direction::Right => Point { x: 1, y: 0 },
+        }
+    }
+
+    pub fn turn_right(&self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+}
+
+// A dense, row-major grid addressed by `Point`, with (0, 0) in the top left
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    // Builds a grid from lines of characters, using `parse_cell` to turn each character into a cell.
+    // Every line must be as wide as the first
+    pub fn parse(input: &str, mut parse_cell: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for line in input.lines() {
+            let mut line_width = 0;
+            for (index, c) in line.char_indices() {
+                let cell = parse_cell(c).ok_or_else(|| {
+                    ParseError::new("Unexpected character", input, &line[index..index + c.len_utf8()])
+                })?;
+                cells.push(cell);
+                line_width += 1;
+            }
+            match width {
+                None => width = Some(line_width),
+                Some(width) if width != line_width => {
+                    return Err(ParseError::new(format!("Expected a row {} wide", width), input, line));
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        match width {
+            Some(width) if width > 0 => Ok(Grid { width, height, cells }),
+            _ => Err(ParseError::new("Expected a grid", input, input)),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: &Point) -> bool {
+        point.x >= 0
+            && point.x < self.width as i64
+            && point.y >= 0
+            && point.y < self.height as i64
+    }
+
+    pub fn get(&self, point: &Point) -> Option<&T> {
+        self.index(point).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, point: &Point) -> Option<&mut T> {
+        self.index(point).map(|index| &mut self.cells[index])
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |index| Point::new((index % width) as i64, (index / width) as i64))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    // The up to four orthogonal neighbours of a point that lie within the grid
+    pub fn neighbours(&self, point: &Point) -> impl Iterator<Item = Point> + '_ {
+        point.neighbours().into_iter().filter(|neighbour| self.contains(neighbour))
+    }
+
+    // As `neighbours`, but including diagonals
+    pub fn neighbours_with_diagonals(&self, point: &Point) -> impl Iterator<Item = Point> + '_ {
+        point.neighbours_with_diagonals().into_iter().filter(|neighbour| self.contains(neighbour))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn index(&self, point: &Point) -> Option<usize> {
+        if self.contains(point) {
+            Some(point.y as usize * self.width + point.x as usize)
+        } else {
+            None
+        }
+    }
+}
+
+impl Grid<char> {
+    pub fn parse_chars(input: &str) -> Result<Self, ParseError> {
+        Self::parse(input, Some)
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for row in self.cells.chunks(self.width) {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use super::*;
+
+    #[test]
+    fn test_parse_chars() {
+        let grid = Grid::parse_chars("ab\ncd\nef\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 3));
+        assert_eq!(grid.get(&Point::new(1, 0)), Some(&'b'));
+        assert_eq!(grid.get(&Point::new(0, 2)), Some(&'e'));
+        assert_eq!(grid.get(&Point::new(2, 0)), None);
+        assert_eq!(grid.get(&Point::new(0, -1)), None);
+        assert_eq!(grid.to_string(), "ab\ncd\nef\n");
+    }
+
+    #[rstest]
+    #[case("12\n3\n", 2, 1, "3")]
+    #[case("12\n3x\n", 2, 2, "x")]
+    #[case("", 1, 1, "")]
+    fn test_parse_error(#[case] input: &str, #[case] line: usize, #[case] column: usize, #[case] text: &str) {
+        let error = Grid::parse(input, |c| c.to_digit(10)).unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (line, column, text));
+    }
+
+    #[rstest]
+    #[case(Point::new(0, 0), 2, 3)]
+    #[case(Point::new(1, 1), 4, 8)]
+    #[case(Point::new(1, 0), 3, 5)]
+    fn test_neighbours(#[case] point: Point, #[case] orthogonal: usize, #[case] all: usize) {
+        let grid = Grid::parse_chars("abc\ndef\nghi").unwrap();
+        assert_eq!(grid.neighbours(&point).count(), orthogonal);
+        assert_eq!(grid.neighbours_with_diagonals(&point).count(), all);
+    }
+
+    #[test]
+    fn test_iter() {
+        let grid = Grid::parse_chars("ab\ncd").unwrap();
+        let cells = grid.iter().collect::<Vec<_>>();
+        assert_eq!(cells, vec![
+            (Point::new(0, 0), &'a'),
+            (Point::new(1, 0), &'b'),
+            (Point::new(0, 1), &'c'),
+            (Point::new(1, 1), &'d'),
+        ]);
+    }
+}
@@ -0,0 +1,101 @@
+use std::fs;
+use std::io;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+pub const STDIN: &str = "-";
+
+pub fn read_input(path: &Path) -> io::Result<String> {
+    if path == Path::new(STDIN) {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        return Ok(input);
+    }
+    fs::read_to_string(path)
+}
+
+// For inputs too large to read into memory at once
+pub fn open_input(path: &Path) -> io::Result<Box<dyn BufRead>> {
+    if path == Path::new(STDIN) {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+    Ok(Box::new(BufReader::new(fs::File::open(path)?)))
+}
+
+// Resolved against the crate rather than the working directory,
+// so the binary can be run from anywhere
+pub fn inputs_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs")
+}
+
+pub fn default_input_path(day: u8) -> PathBuf {
+    inputs_dir().join(format!("day-{}-input.txt", day))
+}
+
+pub fn example_files(day: u8) -> Vec<PathBuf> {
+    example_files_in(&inputs_dir(), day)
+}
+
+pub fn example_files_in(dir: &Path, day: u8) -> Vec<PathBuf> {
+    let prefix = format!("day-{}-", day);
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut examples: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = path.file_name().and_then(|name| name.to_str()).unwrap_or("");
+            name.starts_with(&prefix) && name.contains("example")
+        })
+        .collect();
+    examples.sort();
+    examples
+}
+
+// An unnumbered request prefers an example written for the specific part,
+// then the day's general example, then the first of a numbered set
+pub fn find_example(day: u8, part: u8, number: Option<u8>) -> Option<PathBuf> {
+    let mut candidates = Vec::new();
+    if let Some(number) = number {
+        candidates.push(format!("day-{}-part-{}-example-{}.txt", day, part, number));
+        candidates.push(format!("day-{}-example-{}.txt", day, number));
+    }
+    if number.unwrap_or(1) == 1 {
+        candidates.push(format!("day-{}-part-{}-example.txt", day, part));
+        candidates.push(format!("day-{}-example.txt", day));
+        candidates.push(format!("day-{}-example-1.txt", day));
+    }
+    candidates.into_iter()
+        .map(|name| inputs_dir().join(name))
+        .find(|path| path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use super::*;
+
+    #[rstest]
+    #[case(1, 1, None, Some("day-1-example.txt"))]
+    #[case(3, 1, None, Some("day-3-example.txt"))]
+    #[case(3, 2, None, Some("day-3-part-2-example.txt"))]
+    #[case(8, 1, Some(1), Some("day-8-example.txt"))]
+    #[case(8, 1, Some(2), Some("day-8-example-2.txt"))]
+    #[case(12, 2, None, Some("day-12-example-1.txt"))]
+    #[case(12, 2, Some(3), Some("day-12-example-3.txt"))]
+    #[case(12, 2, Some(4), None)]
+    #[case(11, 1, None, None)]
+    fn test_find_example(#[case] day: u8, #[case] part: u8, #[case] number: Option<u8>, #[case] expected: Option<&str>) {
+        let expected = expected.map(|name| inputs_dir().join(name));
+        assert_eq!(find_example(day, part, number), expected);
+    }
+
+    #[test]
+    fn test_example_files() {
+        let names = example_files(12).into_iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["day-12-example-1.txt", "day-12-example-2.txt", "day-12-example-3.txt"]);
+    }
+}
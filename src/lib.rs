@@ -0,0 +1,30 @@
+pub mod day_1;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_13;
+pub mod answers;
+pub mod external_sort;
+pub mod fetch;
+pub mod generate;
+pub mod grid;
+pub mod input;
+pub mod parallel;
+pub mod parse_error;
+pub mod record;
+pub mod scaffold;
+pub mod solution;
+#[cfg(test)]
+mod stub_server;
+pub mod submit;
+pub mod table;
+pub mod timing;
+pub mod watch;
@@ -1,98 +1,687 @@
-mod day_1;
-mod day_2;
-mod day_3;
-mod day_4;
-mod day_5;
-mod day_6;
-mod day_7;
-mod day_8;
-mod day_9;
-mod day_10;
-mod day_11;
-mod day_12;
-mod day_13;
-
-use clap::Parser;
+use std::env;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::Duration;
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::{ArgAction, Parser, Subcommand, ValueEnum};
+use advent_of_code_2024::answers::{Check, Checked, CheckedPart, KnownAnswers, Summary};
+use advent_of_code_2024::day_1::{Day1, Metric};
+use advent_of_code_2024::day_2::{AllowedDirection, Day2, Report, SafetyPolicy};
+use advent_of_code_2024::fetch::{Fetcher, DEFAULT_BASE_URL, SESSION_VARIABLE};
+use advent_of_code_2024::record::{Outcome, Record};
+use advent_of_code_2024::{answers, day_1, day_2, generate, input, parallel, scaffold, solution, table, watch};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use advent_of_code_2024::solution::{measure, Answer, Day, Measurement, Solution};
+use advent_of_code_2024::submit::{default_history_path, Submitter, Verdict};
+use advent_of_code_2024::table::Row;
+use advent_of_code_2024::timing::format_duration;
 
 #[derive(Parser, Debug)]
-#[command(version, about, long_about = None)]
+#[command(version, about, long_about = None, args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
-    /// Name of the person to greet
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Day of the puzzle to solve
+    #[arg(short, long, required_unless_present_any = ["all", "days", "check"])]
+    day: Option<u8>,
+
+    /// Part of the puzzle to solve, defaults to 1 for a single day and both parts otherwise
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Puzzle input to read instead of inputs/day-N-input.txt, or - for stdin
     #[arg(short, long)]
-    day: u8,
+    input: Option<PathBuf>,
+
+    /// Run against the bundled example input, optionally picking a numbered example
+    #[arg(short, long, num_args = 0..=1, value_name = "N", conflicts_with = "input")]
+    example: Option<Option<u8>>,
+
+    /// Run every registered day and print a summary table
+    #[arg(short, long, conflicts_with_all = ["day", "days", "input"])]
+    all: bool,
+
+    /// Run an inclusive range of days such as 3..9 and print a summary table
+    #[arg(long, value_parser = parse_day_range, conflicts_with_all = ["day", "input"])]
+    days: Option<RangeInclusive<u8>>,
+
+    /// Report parse and solve times separately
+    #[arg(short, long)]
+    time: bool,
+
+    /// Repeat each part N times and report the min, median and max times
+    #[arg(short, long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    bench: Option<u32>,
+
+    /// Compare answers for the selected days, or all days, against the known answers file
+    #[arg(short, long, conflicts_with_all = ["input", "example", "bench"])]
+    check: bool,
+
+    /// Known answers file to use with --check instead of answers.toml
+    #[arg(long, value_name = "PATH", requires = "check")]
+    answers: Option<PathBuf>,
+
+    /// Cap the number of threads used with the parallel feature
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    threads: Option<u32>,
+
+    /// Solve day 1 in bounded memory, sorting N pairs at a time to temporary files and merging them
+    #[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "1000000",
+          value_parser = clap::value_parser!(u64).range(1..), conflicts_with_all = ["all", "days", "check", "bench", "format"])]
+    stream: Option<u64>,
+
+    /// Compare day 1's lists with one or more metrics instead of solving a part
+    #[arg(long, value_delimiter = ',', value_parser = PossibleValuesParser::new(Metric::ALL.map(|metric| metric.name()))
+          .map(|name| Metric::from_name(&name).unwrap()), conflicts_with_all = ["all", "days", "check", "bench", "stream"])]
+    metric: Vec<Metric>,
+
+    /// Explain day 1's distance with the N furthest apart pairs, the IDs in only one list and how often each ID appears
+    #[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "10",
+          conflicts_with_all = ["all", "days", "check", "bench", "stream", "metric"])]
+    report: Option<usize>,
+
+    /// Day 2 safety policy as a TOML file, with any of min-step, max-step, strictly-monotone and direction
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["all", "days", "check", "bench"])]
+    safety_policy: Option<PathBuf>,
+
+    /// Smallest step between adjacent day 2 levels that is still safe, 0 allows plateaus
+    #[arg(long, value_name = "N", conflicts_with_all = ["all", "days", "check", "bench"])]
+    min_step: Option<u64>,
+
+    /// Largest step between adjacent day 2 levels that is still safe
+    #[arg(long, value_name = "N", conflicts_with_all = ["all", "days", "check", "bench"])]
+    max_step: Option<u64>,
 
-    /// Number of times to greet
-    #[arg(short, long, default_value_t = 1)]
-    part: u8,
+    /// Whether day 2 levels must keep going the same way
+    #[arg(long, value_name = "BOOL", action = ArgAction::Set, conflicts_with_all = ["all", "days", "check", "bench"])]
+    strictly_monotone: Option<bool>,
+
+    /// Which way day 2 levels may go
+    #[arg(long, value_parser = PossibleValuesParser::new(AllowedDirection::ALL.map(|direction| direction.name()))
+          .map(|name| AllowedDirection::from_name(&name).unwrap()), conflicts_with_all = ["all", "days", "check", "bench"])]
+    direction: Option<AllowedDirection>,
+
+    /// Output format, json prints one record per day and part or per metric, and csv is only for --report
+    #[arg(short, long, value_enum, default_value_t = Format::Text, conflicts_with = "check")]
+    format: Format,
 }
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Download a day's input and example into inputs/, using the session token in AOC_SESSION
+    Fetch {
+        /// Day of the puzzle to fetch
+        #[arg(short, long)]
+        day: u8,
+
+        /// Server to fetch from, for pointing at a mirror or a local stub
+        #[arg(long, env = "AOC_BASE_URL", default_value = DEFAULT_BASE_URL)]
+        base_url: String,
+    },
+    /// Solve a part against the real input and submit the answer, using the session token in AOC_SESSION
+    Submit {
+        /// Day of the puzzle to submit
+        #[arg(short, long)]
+        day: u8,
+
+        /// Part of the puzzle to submit
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// Server to submit to, for pointing at a mirror or a local stub
+        #[arg(long, env = "AOC_BASE_URL", default_value = DEFAULT_BASE_URL)]
+        base_url: String,
+
+        /// File recording every submission instead of inputs/submissions.toml
+        #[arg(long, value_name = "PATH")]
+        history: Option<PathBuf>,
+    },
+    /// Generate src/day_N.rs and an empty example input, and register the new day
+    NewDay {
+        /// Day of the puzzle to scaffold
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Re-run a day's example tests and solution whenever its source or inputs change
+    Watch {
+        /// Day of the puzzle to watch
+        #[arg(short, long)]
+        day: u8,
+
+        /// How often to check the files for changes
+        #[arg(long, value_name = "MS", default_value_t = 500)]
+        interval: u64,
+    },
+    /// Print a random input for a day, for stress-testing and benchmarking at scale
+    Generate {
+        /// Day of the puzzle to generate an input for
+        #[arg(short, long)]
+        day: u8,
+
+        /// Seed for the random numbers, the same seed always gives the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// Number of lines, grid rows and columns, disk-map digits, stones or machines
+        #[arg(long, default_value_t = 100)]
+        size: usize,
+
+        /// File to write the input to instead of stdout
+        #[arg(short, long, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
+enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
 fn main() {
     let args = Args::parse();
+    if let Some(threads) = args.threads {
+        if let Err(error) = parallel::set_threads(threads as usize) {
+            eprintln!("Ignoring --threads: {}", error);
+        }
+    }
+    if let Some(command) = &args.command {
+        match command {
+            Command::Fetch { day, base_url } => run_fetch(*day, base_url),
+            Command::Submit { day, part, base_url, history } => run_submit(*day, *part, base_url, history.clone()),
+            Command::NewDay { day } => run_new_day(*day),
+            Command::Watch { day, interval } => run_watch(*day, Duration::from_millis(*interval)),
+            Command::Generate { day, seed, size, output } => run_generate(*day, *seed, *size, output.as_deref()),
+        }
+        return;
+    }
+    if let Some(top) = args.report {
+        run_report(&args, top);
+        return;
+    }
+    if args.format == Format::Csv {
+        eprintln!("--format csv only works with --report");
+        process::exit(1);
+    }
+    if let Some(policy) = safety_policy(&args) {
+        run_with_policy(&args, &policy);
+        return;
+    }
+    if !args.metric.is_empty() {
+        run_metrics(&args);
+        return;
+    }
+    if let Some(chunk_size) = args.stream {
+        run_stream(&args, chunk_size as usize);
+        return;
+    }
+    if args.check {
+        run_check(&args);
+        return;
+    }
+    if args.format == Format::Json {
+        run_json(&args);
+        return;
+    }
     match args.day {
-        1 => match args.part {
-            1 => day_1::part_1("inputs/day-1-input.txt"),
-            2 => day_1::part_2("inputs/day-1-input.txt"),
-            _ => panic!("Unknown part: {}", args.part),
-        },
-        2 => match args.part {
-            1 => day_2::part_1("inputs/day-2-input.txt"),
-            2 => day_2::part_2("inputs/day-2-input.txt"),
-            _ => panic!("Unknown part: {}", args.part),
+        Some(day) => run_single(&args, day),
+        None => run_many(&args),
+    }
+}
+
+fn session() -> String {
+    env::var(SESSION_VARIABLE).unwrap_or_else(|_| {
+        eprintln!("Set {} to your adventofcode.com session cookie", SESSION_VARIABLE);
+        process::exit(1);
+    })
+}
+
+fn run_fetch(day: u8, base_url: &str) {
+    let fetched = Fetcher::new(base_url, &session(), &input::inputs_dir())
+        .fetch(day)
+        .unwrap_or_else(|error| {
+            eprintln!("{}", error);
+            process::exit(1);
+        });
+    println!("Saved input to {}", fetched.input.display());
+    match fetched.example {
+        Some(example) => println!("Saved example to {}", example.display()),
+        None => println!("No new example saved for day {}", day),
+    }
+}
+
+fn run_submit(day: u8, part: u8, base_url: &str, history: Option<PathBuf>) {
+    let session = session();
+    let day = find_day(day);
+    let input_path = input::default_input_path(day.number);
+    let input = read_input(&input_path);
+    let answer = match day.run(&input, part).unwrap_or_else(|| panic!("Unknown part: {}", part)) {
+        Ok(run) => run.answer,
+        Err(error) => {
+            eprint!("{}", error.with_file(&input_path).report(&input));
+            process::exit(1);
         }
-        3 => match args.part {
-            1 => day_3::part_1("inputs/day-3-input.txt"),
-            2 => day_3::part_2("inputs/day-3-input.txt"),
-            _ => panic!("Unknown part: {}", args.part),
+    };
+    println!("{}", describe(day.number, part, &answer));
+
+    let history = history.unwrap_or_else(default_history_path);
+    let attempt = Submitter::new(base_url, &session, &history)
+        .submit(day.number, part, &answer)
+        .unwrap_or_else(|error| {
+            eprintln!("Not submitted: {}", error);
+            process::exit(1);
+        });
+    println!("{}", attempt.verdict);
+    if let Some(retry_after) = attempt.retry_after {
+        println!("The next answer can be sent in {}s", retry_after - attempt.submitted_at);
+    }
+    if attempt.verdict != Verdict::Correct {
+        process::exit(1);
+    }
+}
+
+fn run_new_day(day: u8) {
+    let touched = scaffold::new_day(Path::new(env!("CARGO_MANIFEST_DIR")), day)
+        .unwrap_or_else(|error| {
+            eprintln!("{}", error);
+            process::exit(1);
+        });
+    for path in touched {
+        println!("Wrote {}", path.display());
+    }
+}
+
+fn run_generate(day: u8, seed: u64, size: usize, output: Option<&Path>) {
+    let input = generate::generate(day, seed, size).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        process::exit(1);
+    });
+    match output {
+        Some(path) => fs::write(path, input).unwrap_or_else(|error| {
+            eprintln!("Unable to write {}: {}", path.display(), error);
+            process::exit(1);
+        }),
+        None => print!("{}", input),
+    }
+}
+
+fn run_watch(day: u8, interval: Duration) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    println!("Watching day {}, press Ctrl-C to stop", day);
+    let mut runs = 0;
+    watch::watch(root, day, interval, || {
+        runs += 1;
+        println!("[{}] {}", runs, watch::check(root, day));
+    });
+}
+
+fn run_single(args: &Args, day: u8) {
+    let day = find_day(day);
+    let part = args.part.unwrap_or(1);
+    let input_path = input_path(args, day.number, part).unwrap_or_else(|| {
+        report_missing_example(day.number, part, args.example.flatten());
+        process::exit(1);
+    });
+    let input = read_input(&input_path);
+    let measurement = measure(day, &input, part, args.bench.unwrap_or(1)).expect("Clap only allows parts 1 and 2")
+        .unwrap_or_else(|error| {
+            eprint!("{}", error.with_file(&input_path).report(&input));
+            process::exit(1);
+        });
+    print_measurement(args, day.number, part, &measurement);
+}
+
+fn print_measurement(args: &Args, day: u8, part: u8, measurement: &Measurement) {
+    println!("{}", describe(day, part, &measurement.answer));
+    if let Some(runs) = args.bench {
+        println!("Parse ({} runs): {}", runs, measurement.parse);
+        println!("Solve ({} runs): {}", runs, measurement.solve);
+    } else if args.time {
+        println!("Parse: {}", format_duration(measurement.parse.median));
+        println!("Solve: {}", format_duration(measurement.solve.median));
+    }
+}
+
+fn run_metrics(args: &Args) {
+    let (list, other_list) = day_1_lists(args, "--metric");
+    for metric in &args.metric {
+        let value = metric.comparator().compare(&list, &other_list);
+        match args.format {
+            Format::Json => println!("{}", serde_json::json!({ "metric": metric.name(), "value": value })),
+            _ => println!("{}: {}", metric.name(), value),
         }
-        4 => match args.part {
-            1 => day_4::part_1("inputs/day-4-input.txt"),
-            2 => day_4::part_2("inputs/day-4-input.txt"),
-            _ => panic!("Unknown part: {}", args.part),
-        },
-        5 => match args.part {
-            1 => day_5::part_1("inputs/day-5-input.txt"),
-            2 => day_5::part_2("inputs/day-5-input.txt"),
-            _ => panic!("Unknown part: {}", args.part),
-        },
-        6 => match args.part {
-            1 => day_6::part_1("inputs/day-6-input.txt"),
-            2 => day_6::part_2("inputs/day-6-input.txt"),
-            _ => panic!("Unknown part: {}", args.part),
-        },
-        7 => match args.part {
-            1 => day_7::part_1("inputs/day-7-input.txt"),
-            2 => day_7::part_2("inputs/day-7-input.txt"),
-            _ => panic!("Unknown part: {}", args.part),
-        },
-        8 => match args.part {
-            1 => day_8::part_1("inputs/day-8-input.txt"),
-            2 => day_8::part_2("inputs/day-8-input.txt"),
-            _ => panic!("Unknown part: {}", args.part),
-        },
-        9 => match args.part {
-            1 => day_9::part_1("inputs/day-9-input.txt"),
-            2 => day_9::part_2("inputs/day-9-input.txt"),
-            _ => panic!("Unknown part: {}", args.part),
-        },
-        10 => match args.part {
-            1 => day_10::part_1("inputs/day-10-input.txt"),
-            2 => day_10::part_2("inputs/day-10-input.txt"),
-            _ => panic!("Unknown part: {}", args.part),
-        },
-        11 => match args.part {
-            1 => day_11::part_1("inputs/day-11-input.txt"),
-            2 => day_11::part_2("inputs/day-11-input.txt"),
-            _ => panic!("Unknown part: {}", args.part),
-        },
-        12 => match args.part {
-            1 => day_12::part_1("inputs/day-12-input.txt"),
-            2 => day_12::part_2("inputs/day-12-input.txt"),
-            _ => panic!("Unknown part: {}", args.part),
-        },
-        13 => match args.part {
-            1 => day_13::part_1("inputs/day-13-input.txt"),
-            2 => day_13::part_2("inputs/day-13-input.txt"),
-            _ => panic!("Unknown part: {}", args.part),
+    }
+}
+
+fn run_report(args: &Args, top: usize) {
+    let (list, other_list) = day_1_lists(args, "--report");
+    let report = day_1::diff_report(&list, &other_list, top);
+    match args.format {
+        Format::Text => print!("{}", report.to_text()),
+        Format::Json => println!("{}", serde_json::to_string(&report).unwrap()),
+        Format::Csv => print!("{}", report.to_csv()),
+    }
+}
+
+// Parses the selected day 1 input for the modes that analyse its lists rather than solve a part
+fn day_1_lists(args: &Args, mode: &str) -> (Vec<u32>, Vec<u32>) {
+    if args.day != Some(1) {
+        eprintln!("{} only works for day 1", mode);
+        process::exit(1);
+    }
+    let input_path = input_path(args, 1, args.part.unwrap_or(1)).unwrap_or_else(|| {
+        report_missing_example(1, args.part.unwrap_or(1), args.example.flatten());
+        process::exit(1);
+    });
+    let input = read_input(&input_path);
+    Day1::parse(&input).unwrap_or_else(|error| {
+        eprint!("{}", error.with_file(&input_path).report(&input));
+        process::exit(1);
+    })
+}
+
+// The day 2 policy from --safety-policy with any of the other policy flags on top, or None when
+// none of them were given
+fn safety_policy(args: &Args) -> Option<SafetyPolicy> {
+    if args.safety_policy.is_none() && args.min_step.is_none() && args.max_step.is_none()
+        && args.strictly_monotone.is_none() && args.direction.is_none() {
+        return None;
+    }
+    let mut policy = match &args.safety_policy {
+        Some(path) => SafetyPolicy::load(path).unwrap_or_else(|error| {
+            eprintln!("{}", error);
+            process::exit(1);
+        }),
+        None => SafetyPolicy::default(),
+    };
+    policy.min_step = args.min_step.unwrap_or(policy.min_step);
+    policy.max_step = args.max_step.unwrap_or(policy.max_step);
+    policy.strictly_monotone = args.strictly_monotone.unwrap_or(policy.strictly_monotone);
+    policy.direction = args.direction.unwrap_or(policy.direction);
+    if let Err(error) = policy.validate() {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+    Some(policy)
+}
+
+fn run_with_policy(args: &Args, policy: &SafetyPolicy) {
+    if args.day != Some(2) {
+        eprintln!("Safety policies only apply to day 2");
+        process::exit(1);
+    }
+    let part = args.part.unwrap_or(1);
+    let input_path = input_path(args, 2, part).unwrap_or_else(|| {
+        report_missing_example(2, part, args.example.flatten());
+        process::exit(1);
+    });
+    let input = read_input(&input_path);
+    let is_safe: fn(&Report, &SafetyPolicy) -> bool = match part {
+        1 => day_2::is_safe,
+        2 => day_2::is_safe_with_problem_dampener,
+        _ => unreachable!("Clap only allows parts 1 and 2"),
+    };
+    let runs = args.bench.unwrap_or(1);
+    let measured = solution::measure_with(&input, runs, Day2::parse, |reports| {
+        reports.iter().filter(|report| is_safe(report, policy)).count().into()
+    });
+    if args.format == Format::Json {
+        let outcome = match measured {
+            Ok(measurement) => Outcome::solved(measurement.answer, runs, measurement.parse, measurement.solve),
+            Err(error) => Outcome::ParseError { error: error.into() },
+        };
+        let record = Record::new(2, part, Some(&input_path), Some(&input), outcome);
+        println!("{}", record.to_json());
+        if !record.is_solved() {
+            process::exit(1);
         }
-        _ => panic!("Unknown day: {}", args.day),
+        return;
+    }
+    let measurement = measured.unwrap_or_else(|error| {
+        eprint!("{}", error.with_file(&input_path).report(&input));
+        process::exit(1);
+    });
+    print_measurement(args, 2, part, &measurement);
+}
+
+fn run_stream(args: &Args, chunk_size: usize) {
+    if args.day != Some(1) {
+        eprintln!("--stream only works for day 1");
+        process::exit(1);
+    }
+    let part = args.part.unwrap_or(1);
+    let input_path = input_path(args, 1, part).unwrap_or_else(|| {
+        report_missing_example(1, part, args.example.flatten());
+        process::exit(1);
+    });
+    let reader = input::open_input(&input_path).unwrap_or_else(|error| {
+        eprintln!("Unable to read {}: {}", input_path.display(), error);
+        process::exit(1);
+    });
+    let totals = day_1::stream_totals(reader, chunk_size).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        process::exit(1);
+    });
+    let answer = match part {
+        1 => totals.distance,
+        _ => totals.similarity,
+    };
+    println!("{}", describe(1, part, &Answer::U64(answer)));
+}
+
+fn run_many(args: &Args) {
+    let jobs = jobs(selected_days(args), selected_parts(args));
+    // Everything is worked out before printing, so days can run concurrently with the parallel feature
+    let rows = parallel::iter(&jobs)
+        .map(|&(day, part)| (day.number, part, row(args, day, part)))
+        .collect::<Vec<_>>();
+    print!("{}", table::render(&rows, args.bench.is_some()));
+}
+
+fn jobs(days: Vec<&'static Day>, parts: Vec<u8>) -> Vec<(&'static Day, u8)> {
+    days.into_iter()
+        .flat_map(|day| parts.iter().map(move |&part| (day, part)))
+        .collect()
+}
+
+// Parse errors are reported to stderr straight away, as there's only room in the table to say one happened
+fn row(args: &Args, day: &Day, part: u8) -> Row {
+    let Some(input_path) = input_path(args, day.number, part) else {
+        return Row::NoExample;
+    };
+    let Ok(input) = input::read_input(&input_path) else {
+        return Row::NoInput;
+    };
+    match measure(day, &input, part, args.bench.unwrap_or(1)).expect("Clap only allows parts 1 and 2") {
+        Ok(measurement) => Row::Measured(measurement),
+        Err(error) => {
+            eprint!("{}", error.with_file(&input_path).report(&input));
+            Row::ParseError
+        }
+    }
+}
+
+fn run_json(args: &Args) {
+    let (days, parts) = match args.day {
+        Some(day) => {
+            let day = find_day(day);
+            (vec![day], vec![args.part.unwrap_or(1)])
+        }
+        None => (selected_days(args), selected_parts(args)),
+    };
+    let records = parallel::iter(&jobs(days, parts))
+        .map(|&(day, part)| record(args, day, part))
+        .collect::<Vec<_>>();
+    let mut all_solved = true;
+    for record in records {
+        all_solved &= record.is_solved();
+        println!("{}", record.to_json());
+    }
+    if args.day.is_some() && !all_solved {
+        process::exit(1);
+    }
+}
+
+fn record(args: &Args, day: &Day, part: u8) -> Record {
+    let Some(input_path) = input_path(args, day.number, part) else {
+        return Record::new(day.number, part, None, None, Outcome::MissingInput);
+    };
+    let Ok(input) = input::read_input(&input_path) else {
+        return Record::new(day.number, part, Some(&input_path), None, Outcome::MissingInput);
+    };
+    let runs = args.bench.unwrap_or(1);
+    let outcome = match measure(day, &input, part, runs).expect("Clap only allows parts 1 and 2") {
+        Ok(measurement) => Outcome::solved(measurement.answer, runs, measurement.parse, measurement.solve),
+        Err(error) => Outcome::ParseError { error: error.into() },
+    };
+    Record::new(day.number, part, Some(&input_path), Some(&input), outcome)
+}
+
+fn run_check(args: &Args) {
+    let answers_path = args.answers.clone().unwrap_or_else(answers::default_answers_path);
+    let known_answers = KnownAnswers::load(&answers_path).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        process::exit(1);
+    });
+    let checked = known_answers.check_days(&selected_days(args), &selected_parts(args), input::default_input_path);
+
+    println!("{:>3}  {:>4}  {:>20}  {:>20}  {:>7}", "Day", "Part", "Answer", "Expected", "Status");
+    for CheckedPart { day, part, checked } in &checked {
+        let (answer, expected, status) = match checked {
+            Checked::NoInput => ("no input".to_string(), String::new(), "unknown"),
+            Checked::ParseError { .. } => ("parse error".to_string(), String::new(), "FAIL"),
+            Checked::Answered { answer, check: Check::Pass } => (answer.to_string(), answer.to_string(), "pass"),
+            Checked::Answered { answer, check: Check::Fail { expected } } => (answer.to_string(), expected.clone(), "FAIL"),
+            Checked::Answered { answer, check: Check::Unknown } => (answer.to_string(), "?".to_string(), "unknown"),
+        };
+        println!("{:>3}  {:>4}  {:>20}  {:>20}  {:>7}", day, part, answer, expected, status);
+        if let Checked::ParseError { report } = checked {
+            eprint!("{}", report);
+        }
+    }
+    let summary = Summary::of(&checked);
+    println!("{} passed, {} failed, {} unknown", summary.passed, summary.failed, summary.unknown);
+    if summary.failed > 0 {
+        process::exit(1);
+    }
+}
+
+fn find_day(day: u8) -> &'static Day {
+    solution::find_day(day).unwrap_or_else(|| {
+        let days = solution::DAYS.iter().map(|day| day.number.to_string()).collect::<Vec<_>>();
+        eprintln!("Day {} isn't registered, the registered days are {}", day, days.join(", "));
+        process::exit(1);
+    })
+}
+
+fn selected_days(args: &Args) -> Vec<&'static Day> {
+    solution::DAYS.iter()
+        .filter(|day| match (args.day, &args.days) {
+            (Some(number), _) => day.number == number,
+            (None, Some(days)) => days.contains(&day.number),
+            (None, None) => true,
+        })
+        .collect()
+}
+
+fn selected_parts(args: &Args) -> Vec<u8> {
+    match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    }
+}
+
+fn input_path(args: &Args, day: u8, part: u8) -> Option<PathBuf> {
+    match (&args.input, args.example) {
+        (Some(path), _) => Some(path.clone()),
+        (None, Some(number)) => input::find_example(day, part, number),
+        (None, None) => Some(input::default_input_path(day)),
+    }
+}
+
+fn read_input(input_path: &Path) -> String {
+    input::read_input(input_path).unwrap_or_else(|error| {
+        eprintln!("Unable to read {}: {}", input_path.display(), error);
+        process::exit(1);
+    })
+}
+
+fn parse_day_range(value: &str) -> Result<RangeInclusive<u8>, String> {
+    let parse_day = |day: &str| day.trim().parse::<u8>()
+        .map_err(|_| format!("'{}' is not a day number", day));
+    match value.split_once("..") {
+        Some((start, end)) => {
+            let end = end.strip_prefix('=').unwrap_or(end);
+            Ok(parse_day(start)?..=parse_day(end)?)
+        }
+        None => {
+            let day = parse_day(value)?;
+            Ok(day..=day)
+        }
+    }
+}
+
+fn report_missing_example(day: u8, part: u8, number: Option<u8>) {
+    match number {
+        Some(number) => eprintln!("No example {} for day {} part {}", number, day, part),
+        None => eprintln!("No example for day {} part {}", day, part),
+    }
+    let examples = input::example_files(day);
+    if examples.is_empty() {
+        eprintln!("Day {} has no example inputs", day);
+        return;
+    }
+    eprintln!("Available examples for day {}:", day);
+    for example in examples {
+        eprintln!("  {}", example.display());
+    }
+}
+
+fn describe(day: u8, part: u8, answer: &Answer) -> String {
+    match (day, part) {
+        (1, 1) => format!("Sum of diffs: {}", answer),
+        (1, 2) => format!("Similarity score: {}", answer),
+        (2, _) => format!("Safe reports: {}", answer),
+        (3, _) => format!("Sum of multiplications: {}", answer),
+        (4, _) => format!("XMAS appears {} times", answer),
+        (5, part) => format!("Part {}: {}", part, answer),
+        (6, 1) => format!("Positions travelled: {}", answer),
+        (6, 2) => format!("Positions we can put stuff to make a paradox: {}", answer),
+        (8, _) => format!("Unique antinode positions: {}", answer),
+        (9, _) => format!("Checksum: {}", answer),
+        (10, _) => format!("Total: {}", answer),
+        (11, 1) => format!("{} stones total after 25 blinks", answer),
+        (11, 2) => format!("{} stones total after 75 blinks", answer),
+        (12, _) => format!("Fence price: {}", answer),
+        (13, _) => format!("Tokens Spent: {}", answer),
+        _ => format!("{}", answer),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use super::*;
+
+    #[rstest]
+    #[case("3..9", Ok(3..=9))]
+    #[case("3..=9", Ok(3..=9))]
+    #[case("5", Ok(5..=5))]
+    #[case("a..9", Err("'a' is not a day number".to_string()))]
+    fn test_parse_day_range(#[case] value: &str, #[case] expected: Result<RangeInclusive<u8>, String>) {
+        assert_eq!(parse_day_range(value), expected);
+    }
+
+    #[rstest]
+    #[case(&["-d", "1", "-p", "3"])]
+    #[case(&["--all", "-p", "0"])]
+    #[case(&["-d", "1", "--stream", "-f", "json"])]
+    fn test_rejected_args(#[case] args: &[&str]) {
+        assert!(Args::try_parse_from([&["AdventOfCode2024"], args].concat()).is_err());
     }
 }
@@ -0,0 +1,28 @@
+// Per-item loops go through `iter`, which is a rayon parallel iterator when the `parallel`
+// feature is enabled and a plain slice iterator otherwise. Callers also need
+// `#[cfg(feature = "parallel")] use rayon::prelude::*;` for the parallel adapters
+
+#[cfg(feature = "parallel")]
+pub fn iter<T: Sync>(items: &[T]) -> rayon::slice::Iter<'_, T> {
+    use rayon::prelude::*;
+    items.par_iter()
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn iter<T>(items: &[T]) -> std::slice::Iter<'_, T> {
+    items.iter()
+}
+
+// Caps the number of worker threads. Must be called before anything runs in parallel
+#[cfg(feature = "parallel")]
+pub fn set_threads(threads: usize) -> Result<(), String> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+        .map_err(|error| error.to_string())
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn set_threads(_threads: usize) -> Result<(), String> {
+    Err("built without the parallel feature, so everything runs on one thread".to_string())
+}
@@ -0,0 +1,155 @@
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ParseError {
+    pub file: Option<PathBuf>,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    // `fragment` must be a slice of `input`, which is how the line and column are found.
+    // Pass an empty slice at the end of the input to report something missing
+    pub fn new(message: impl Into<String>, input: &str, fragment: &str) -> Self {
+        let before = before(input, fragment);
+        let line_start = before.rfind('\n').map(|index| index + 1).unwrap_or(0);
+        ParseError {
+            file: None,
+            line: line_of(input, fragment),
+            column: before[line_start..].chars().count() + 1,
+            text: fragment.to_string(),
+            message: message.into(),
+        }
+    }
+
+    // For errors found while parsing a piece of the input that starts further down
+    pub fn offset_by_lines(self, lines: usize) -> Self {
+        ParseError {
+            line: self.line + lines,
+            ..self
+        }
+    }
+
+    pub fn with_file(self, file: &Path) -> Self {
+        ParseError {
+            file: Some(file.to_path_buf()),
+            ..self
+        }
+    }
+
+    // Renders a compiler-style diagnostic pointing at the offending text
+    pub fn report(&self, input: &str) -> String {
+        let source_line = input.lines().nth(self.line - 1).unwrap_or("");
+        let gutter = " ".repeat(self.line.to_string().len());
+        let underline = "^".repeat(self.text.trim_end_matches(['\r', '\n']).chars().count().max(1));
+        let mut report = format!(
+            "error: {}\n{}--> {}:{}:{}\n{} |\n{} | {}\n{} | {}{}\n",
+            self.message,
+            gutter,
+            self.file_name(),
+            self.line,
+            self.column,
+            gutter,
+            self.line,
+            source_line.trim_end_matches('\r'),
+            gutter,
+            " ".repeat(self.column - 1),
+            underline,
+        );
+        if input.contains("\r\n") {
+            report.push_str(&format!("{} = note: the input has Windows (CRLF) line endings\n", gutter));
+        }
+        report
+    }
+
+    fn file_name(&self) -> String {
+        match &self.file {
+            Some(file) if file == Path::new("-") => "<stdin>".to_string(),
+            Some(file) => file.display().to_string(),
+            None => "<input>".to_string(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}: {}", self.file_name(), self.line, self.column, self.message)?;
+        if !self.text.is_empty() {
+            write!(f, " (found {:?})", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+// The line `fragment`, a slice of `input`, starts on, counting from 1
+pub fn line_of(input: &str, fragment: &str) -> usize {
+    before(input, fragment).matches('\n').count() + 1
+}
+
+fn before<'a>(input: &'a str, fragment: &str) -> &'a str {
+    let offset = (fragment.as_ptr() as usize)
+        .saturating_sub(input.as_ptr() as usize)
+        .min(input.len());
+    &input[..floor_char_boundary(input, offset)]
+}
+
+fn floor_char_boundary(input: &str, mut offset: usize) -> usize {
+    while !input.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use super::*;
+
+    #[rstest]
+    #[case("12 34\n56 x8\n", 9, 2, 4)]
+    #[case("12 34\n56 x8\n", 0, 1, 1)]
+    #[case("12 34\n56 x8\n", 12, 3, 1)]
+    #[case("é1\nab", 4, 2, 1)]
+    #[case("é1\nab", 2, 1, 2)]
+    fn test_position(#[case] input: &str, #[case] offset: usize, #[case] line: usize, #[case] column: usize) {
+        let error = ParseError::new("bad", input, &input[offset..]);
+        assert_eq!((error.line, error.column), (line, column));
+    }
+
+    #[rstest]
+    #[case("ab\ncd\nef", 0, 1)]
+    #[case("ab\ncd\nef", 3, 2)]
+    #[case("ab\ncd\nef", 8, 3)]
+    fn test_line_of(#[case] input: &str, #[case] offset: usize, #[case] line: usize) {
+        assert_eq!(line_of(input, &input[offset..]), line);
+    }
+
+    #[test]
+    fn test_report() {
+        let input = "3   4\n4   x\n";
+        let error = ParseError::new("expected a number", input, &input[10..11])
+            .with_file(Path::new("inputs/day-1-input.txt"));
+        let expected = "\
+error: expected a number
+ --> inputs/day-1-input.txt:2:5
+  |
+2 | 4   x
+  |     ^
+";
+        assert_eq!(error.report(input), expected);
+    }
+
+    #[test]
+    fn test_report_notes_windows_line_endings() {
+        let input = "47|53\r\n\r\n75,47\r\n";
+        let error = ParseError::new("expected a blank line", input, &input[input.len()..]);
+        assert!(error.report(input).contains("Windows (CRLF) line endings"));
+    }
+}
@@ -0,0 +1,149 @@
+use std::path::Path;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use crate::parse_error::ParseError;
+use crate::solution::Answer;
+use crate::timing::Stats;
+
+// One line of `--format json` output, describing a single day and part
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub input: Option<String>,
+    pub input_hash: Option<String>,
+    #[serde(flatten)]
+    pub outcome: Outcome,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Outcome {
+    Solved {
+        answer: Answer,
+        answer_type: &'static str,
+        timings: Timings,
+    },
+    ParseError {
+        error: Diagnostic,
+    },
+    MissingInput,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Timings {
+    pub runs: u32,
+    pub parse: DurationStats,
+    pub solve: DurationStats,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DurationStats {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub max_ns: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    pub text: String,
+}
+
+impl Record {
+    pub fn new(day: u8, part: u8, input_path: Option<&Path>, input: Option<&str>, outcome: Outcome) -> Self {
+        Record {
+            day,
+            part,
+            input: input_path.map(|path| path.display().to_string()),
+            input_hash: input.map(hash_input),
+            outcome,
+        }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        matches!(self.outcome, Outcome::Solved { .. })
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Records only contain serializable data")
+    }
+}
+
+impl Outcome {
+    pub fn solved(answer: Answer, runs: u32, parse: Stats, solve: Stats) -> Self {
+        Outcome::Solved {
+            answer_type: answer.type_name(),
+            answer,
+            timings: Timings {
+                runs,
+                parse: parse.into(),
+                solve: solve.into(),
+            },
+        }
+    }
+}
+
+impl From<Stats> for DurationStats {
+    fn from(stats: Stats) -> Self {
+        DurationStats {
+            min_ns: stats.min.as_nanos() as u64,
+            median_ns: stats.median.as_nanos() as u64,
+            max_ns: stats.max.as_nanos() as u64,
+        }
+    }
+}
+
+impl From<ParseError> for Diagnostic {
+    fn from(error: ParseError) -> Self {
+        Diagnostic {
+            line: error.line,
+            column: error.column,
+            message: error.message,
+            text: error.text,
+        }
+    }
+}
+
+// Hex encoded SHA-256 of the input, so records from the same input can be matched up
+pub fn hash_input(input: &str) -> String {
+    format!("{:x}", Sha256::digest(input.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use super::*;
+
+    #[test]
+    fn test_hash_input() {
+        assert_eq!(hash_input("abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    }
+
+    #[test]
+    fn test_solved_record() {
+        let stats = Stats::from_samples(vec![Duration::from_micros(3)]).unwrap();
+        let outcome = Outcome::solved(Answer::U64(11), 1, stats, stats);
+        let record = Record::new(1, 2, Some(Path::new("inputs/day-1-example.txt")), Some("abc"), outcome);
+        let expected = concat!(
+            r#"{"day":1,"part":2,"input":"inputs/day-1-example.txt","#,
+            r#""input_hash":"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad","#,
+            r#""status":"solved","answer":11,"answer_type":"u64","#,
+            r#""timings":{"runs":1,"parse":{"min_ns":3000,"median_ns":3000,"max_ns":3000},"#,
+            r#""solve":{"min_ns":3000,"median_ns":3000,"max_ns":3000}}}"#,
+        );
+        assert_eq!(record.to_json(), expected);
+    }
+
+    #[test]
+    fn test_parse_error_record() {
+        let input = "3   4\n4   x\n";
+        let error = ParseError::new("Expected a number", input, &input[10..11]);
+        let record = Record::new(1, 1, None, Some(input), Outcome::ParseError { error: error.into() });
+        let json = record.to_json();
+        assert!(json.contains(r#""status":"parse_error","error":{"line":2,"column":5,"message":"Expected a number","text":"x"}"#));
+        assert!(!record.is_solved());
+    }
+}
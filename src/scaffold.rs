@@ -0,0 +1,171 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use itertools::Itertools;
+use regex::Regex;
+
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+
+// Creates src/day_N.rs and an empty example input, then registers the day in the library,
+// the solution registry and the benchmarks. Returns every file it touched
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    let module_path = root.join("src").join(format!("day_{}.rs", day));
+    if module_path.exists() {
+        return Err(format!("{} already exists", module_path.display()));
+    }
+
+    // Work out every edit before writing anything, so a failure leaves the tree untouched
+    let registrations = [
+        (root.join("src/lib.rs"), register_module as fn(&str, u8) -> Result<String, String>),
+        (root.join("src/solution.rs"), register_solution),
+        (root.join("benches/days.rs"), register_bench),
+    ];
+    let mut edits = Vec::new();
+    for (path, register) in registrations {
+        let contents = fs::read_to_string(&path)
+            .map_err(|error| format!("Unable to read {}: {}", path.display(), error))?;
+        let updated = register(&contents, day)
+            .map_err(|error| format!("Unable to register day {} in {}: {}", day, path.display(), error))?;
+        edits.push((path, updated));
+    }
+
+    let mut touched = Vec::new();
+    write(&module_path, &TEMPLATE.replace("{{day}}", &day.to_string()))?;
+    touched.push(module_path);
+
+    let example_path = root.join("inputs").join(format!("day-{}-example.txt", day));
+    if !example_path.exists() {
+        write(&example_path, "")?;
+        touched.push(example_path);
+    }
+
+    for (path, contents) in edits {
+        write(&path, &contents)?;
+        touched.push(path);
+    }
+    Ok(touched)
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|error| format!("Unable to write {}: {}", path.display(), error))
+}
+
+fn register_module(contents: &str, day: u8) -> Result<String, String> {
+    let pattern = Regex::new(r"^pub mod day_(\d+);$").unwrap();
+    insert_in_order(contents, &pattern, day, &format!("pub mod day_{};", day))
+}
+
+fn register_solution(contents: &str, day: u8) -> Result<String, String> {
+    let contents = add_to_use_list(contents, "use crate::{", day)?;
+    let pattern = Regex::new(r"^\s*Day \{ number: (\d+),").unwrap();
+    let line = format!("    Day {{ number: {}, run: run::<day_{}::Day{}> }},", day, day, day);
+    insert_in_order(&contents, &pattern, day, &line)
+}
+
+fn register_bench(contents: &str, day: u8) -> Result<String, String> {
+    let contents = add_to_use_list(contents, "use advent_of_code_2024::{day_", day)?;
+    let pattern = Regex::new(r"^\s*bench_day::<day_\d+::Day\d+>\(c, (\d+)\);$").unwrap();
+    let line = format!("    bench_day::<day_{}::Day{}>(c, {});", day, day, day);
+    insert_in_order(&contents, &pattern, day, &line)
+}
+
+// Adds `line` after the last line matching `pattern` whose captured day comes before `day`,
+// so the existing days stay in numeric order
+fn insert_in_order(contents: &str, pattern: &Regex, day: u8, line: &str) -> Result<String, String> {
+    let mut lines = contents.lines().collect_vec();
+    let days = lines.iter()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let captures = pattern.captures(line)?;
+            Some((index, captures[1].parse::<u8>().ok()?))
+        })
+        .collect_vec();
+    if days.iter().any(|(_, existing)| *existing == day) {
+        return Err("already registered".to_string());
+    }
+    let index = match days.iter().rev().find(|(_, existing)| *existing < day) {
+        Some((index, _)) => index + 1,
+        None => days.first().ok_or("no existing days to follow")?.0,
+    };
+    lines.insert(index, line);
+    Ok(lines.join("\n") + "\n")
+}
+
+// Adds day_N to a `use ...::{day_1, day_10, ...};` list, keeping it sorted the way rustfmt does
+fn add_to_use_list(contents: &str, prefix: &str, day: u8) -> Result<String, String> {
+    let start = contents.find(prefix).ok_or("no list of day modules")?;
+    let open = start + contents[start..].find('{').unwrap() + 1;
+    let close = open + contents[open..].find('}').ok_or("unterminated list of day modules")?;
+    let mut modules = contents[open..close].split(',')
+        .map(|module| module.trim().to_string())
+        .filter(|module| !module.is_empty())
+        .collect_vec();
+    modules.push(format!("day_{}", day));
+    modules.sort();
+    Ok(format!("{}{}{}", &contents[..open], modules.join(", "), &contents[close..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solution::find_day;
+    use super::*;
+
+    fn copy_of_crate() -> tempfile::TempDir {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let dir = tempfile::tempdir().unwrap();
+        for file in ["src/lib.rs", "src/solution.rs", "src/day_13.rs", "benches/days.rs"] {
+            let destination = dir.path().join(file);
+            fs::create_dir_all(destination.parent().unwrap()).unwrap();
+            fs::copy(root.join(file), destination).unwrap();
+        }
+        fs::create_dir(dir.path().join("inputs")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_new_day() {
+        let dir = copy_of_crate();
+        let day = (1..=25).find(|day| find_day(*day).is_none()).expect("A day still to be solved");
+        let touched = new_day(dir.path(), day).unwrap();
+        assert_eq!(touched.len(), 5);
+
+        let module = fs::read_to_string(dir.path().join(format!("src/day_{}.rs", day))).unwrap();
+        assert!(module.contains(&format!("impl Solution for Day{} {{", day)));
+        assert!(module.contains(&format!("#[case(\"inputs/day-{}-example.txt\", Answer::U64(0))]", day)));
+        assert_eq!(module.matches("#[ignore = ").count(), 2);
+        assert_eq!(fs::read_to_string(dir.path().join(format!("inputs/day-{}-example.txt", day))).unwrap(), "");
+
+        let lib = fs::read_to_string(dir.path().join("src/lib.rs")).unwrap();
+        assert!(lib.contains(&format!("pub mod day_{};\n", day)));
+        let solution = fs::read_to_string(dir.path().join("src/solution.rs")).unwrap();
+        assert!(solution.contains(&format!("day_{}, ", day)));
+        assert!(solution.contains(&format!("    Day {{ number: {}, run: run::<day_{}::Day{}> }},\n", day, day, day)));
+        let benches = fs::read_to_string(dir.path().join("benches/days.rs")).unwrap();
+        assert!(benches.contains(&format!("day_{}, ", day)));
+        assert!(benches.contains(&format!("    bench_day::<day_{}::Day{}>(c, {});\n", day, day, day)));
+    }
+
+    #[test]
+    fn test_new_day_refuses_existing_day() {
+        let dir = copy_of_crate();
+        let lib = fs::read_to_string(dir.path().join("src/lib.rs")).unwrap();
+        assert!(new_day(dir.path(), 13).unwrap_err().contains("already exists"));
+        fs::remove_file(dir.path().join("src/day_13.rs")).unwrap();
+        assert!(new_day(dir.path(), 13).unwrap_err().contains("already registered"));
+        assert!(!dir.path().join("src/day_13.rs").exists());
+        assert_eq!(fs::read_to_string(dir.path().join("src/lib.rs")).unwrap(), lib);
+    }
+
+    #[test]
+    fn test_insert_in_order() {
+        let pattern = Regex::new(r"^pub mod day_(\d+);$").unwrap();
+        let contents = "pub mod day_1;\npub mod day_3;\npub mod grid;\n";
+        assert_eq!(
+            insert_in_order(contents, &pattern, 2, "pub mod day_2;").unwrap(),
+            "pub mod day_1;\npub mod day_2;\npub mod day_3;\npub mod grid;\n"
+        );
+        assert_eq!(
+            insert_in_order(contents, &pattern, 4, "pub mod day_4;").unwrap(),
+            "pub mod day_1;\npub mod day_3;\npub mod day_4;\npub mod grid;\n"
+        );
+    }
+}
@@ -0,0 +1,244 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+use serde::{Serialize, Serializer};
+use crate::parse_error::ParseError;
+use crate::timing::Stats;
+use crate::{day_1, day_10, day_11, day_12, day_13, day_2, day_3, day_4, day_5, day_6, day_7, day_8, day_9};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Answer {
+    U64(u64),
+    I64(i64),
+    String(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::U64(value) => value.fmt(f),
+            Answer::I64(value) => value.fmt(f),
+            Answer::String(value) => value.fmt(f),
+        }
+    }
+}
+
+impl Answer {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Answer::U64(_) => "u64",
+            Answer::I64(_) => "i64",
+            Answer::String(_) => "string",
+        }
+    }
+}
+
+// Numbers are written as JSON numbers rather than wrapped in the variant name
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::U64(value) => serializer.serialize_u64(*value),
+            Answer::I64(value) => serializer.serialize_i64(*value),
+            Answer::String(value) => serializer.serialize_str(value),
+        }
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Answer::U64(value)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Answer::U64(value as u64)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::U64(value as u64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::I64(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::String(value)
+    }
+}
+
+pub trait Solution {
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part_1(input: &Self::Input) -> Answer;
+    fn part_2(input: &Self::Input) -> Answer;
+}
+
+pub struct Run {
+    pub answer: Answer,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+pub struct Day {
+    pub number: u8,
+    run: fn(&str, u8) -> Option<Result<Run, ParseError>>,
+}
+
+impl Day {
+    pub fn run(&self, input: &str, part: u8) -> Option<Result<Run, ParseError>> {
+        (self.run)(input, part)
+    }
+}
+
+fn run<S: Solution>(input: &str, part: u8) -> Option<Result<Run, ParseError>> {
+    let solve: fn(&S::Input) -> Answer = match part {
+        1 => S::part_1,
+        2 => S::part_2,
+        _ => return None,
+    };
+    Some(run_with(input, S::parse, solve))
+}
+
+// Times parsing and solving separately, for any parser and solver rather than only a registered day's parts
+pub fn run_with<I>(
+    input: &str,
+    parse: impl Fn(&str) -> Result<I, ParseError>,
+    solve: impl Fn(&I) -> Answer,
+) -> Result<Run, ParseError> {
+    let start = Instant::now();
+    let parsed = parse(input)?;
+    let parse_time = start.elapsed();
+
+    let start = Instant::now();
+    let answer = solve(&parsed);
+    let solve_time = start.elapsed();
+
+    Ok(Run {
+        answer,
+        parse_time,
+        solve_time,
+    })
+}
+
+// The answer from the first of `runs` runs, with the spread of parse and solve times across all of them
+pub struct Measurement {
+    pub answer: Answer,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+// Like `Day::run`, None for a part the day doesn't have. At least one run is always made
+pub fn measure(day: &Day, input: &str, part: u8, runs: u32) -> Option<Result<Measurement, ParseError>> {
+    let mut samples = Vec::new();
+    for _ in 0..runs.max(1) {
+        match day.run(input, part)? {
+            Ok(run) => samples.push(run),
+            Err(error) => return Some(Err(error)),
+        }
+    }
+    Some(Ok(Measurement::from_runs(samples)))
+}
+
+// Like `measure`, but with `run_with`'s parser and solver
+pub fn measure_with<I>(
+    input: &str,
+    runs: u32,
+    parse: impl Fn(&str) -> Result<I, ParseError>,
+    solve: impl Fn(&I) -> Answer,
+) -> Result<Measurement, ParseError> {
+    let samples = (0..runs.max(1))
+        .map(|_| run_with(input, &parse, &solve))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Measurement::from_runs(samples))
+}
+
+impl Measurement {
+    fn from_runs(runs: Vec<Run>) -> Self {
+        Measurement {
+            answer: runs[0].answer.clone(),
+            parse: Stats::from_samples(runs.iter().map(|run| run.parse_time).collect())
+                .expect("At least one run"),
+            solve: Stats::from_samples(runs.iter().map(|run| run.solve_time).collect())
+                .expect("At least one run"),
+        }
+    }
+}
+
+pub static DAYS: &[Day] = &[
+    Day { number: 1, run: run::<day_1::Day1> },
+    Day { number: 2, run: run::<day_2::Day2> },
+    Day { number: 3, run: run::<day_3::Day3> },
+    Day { number: 4, run: run::<day_4::Day4> },
+    Day { number: 5, run: run::<day_5::Day5> },
+    Day { number: 6, run: run::<day_6::Day6> },
+    Day { number: 7, run: run::<day_7::Day7> },
+    Day { number: 8, run: run::<day_8::Day8> },
+    Day { number: 9, run: run::<day_9::Day9> },
+    Day { number: 10, run: run::<day_10::Day10> },
+    Day { number: 11, run: run::<day_11::Day11> },
+    Day { number: 12, run: run::<day_12::Day12> },
+    Day { number: 13, run: run::<day_13::Day13> },
+];
+
+pub fn find_day(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::read_to_string;
+    use rstest::rstest;
+    use super::*;
+
+    #[rstest]
+    #[case(1, 1, Answer::U64(11))]
+    #[case(1, 2, Answer::U64(31))]
+    #[case(9, 2, Answer::U64(2858))]
+    fn test_solve_example(#[case] day: u8, #[case] part: u8, #[case] expected: Answer) {
+        let input = read_to_string(format!("inputs/day-{}-example.txt", day)).unwrap();
+        let day = find_day(day).expect("Day should be registered");
+        assert_eq!(day.run(&input, part).map(|run| run.unwrap().answer), Some(expected));
+    }
+
+    #[test]
+    fn test_unknown_part() {
+        let day = find_day(1).expect("Day should be registered");
+        let input = read_to_string("inputs/day-1-example.txt").unwrap();
+        assert!(day.run(&input, 3).is_none());
+    }
+
+    #[rstest]
+    #[case(0, Answer::U64(11))]
+    #[case(5, Answer::U64(11))]
+    fn test_measure(#[case] runs: u32, #[case] expected: Answer) {
+        let input = read_to_string("inputs/day-1-example.txt").unwrap();
+        let measurement = measure(find_day(1).unwrap(), &input, 1, runs).unwrap().unwrap();
+        assert_eq!(measurement.answer, expected);
+        assert!(measurement.solve.min <= measurement.solve.median && measurement.solve.median <= measurement.solve.max);
+    }
+
+    #[test]
+    fn test_measure_with() {
+        let parse = |input: &str| input.lines().map(|line| line.parse::<u64>()).collect::<Result<Vec<_>, _>>()
+            .map_err(|_| ParseError::new("Expected a number", input, input));
+        let measurement = measure_with("1\n2\n3\n", 3, parse, |numbers| numbers.iter().sum::<u64>().into()).unwrap();
+        assert_eq!(measurement.answer, Answer::U64(6));
+        assert!(measure_with("1\nx\n", 3, parse, |numbers| numbers.len().into()).is_err());
+    }
+
+    #[test]
+    fn test_measure_failures() {
+        let day = find_day(1).unwrap();
+        assert!(measure(day, "3   4\n", 3, 1).is_none());
+        assert!(matches!(measure(day, "3   x\n", 1, 5), Some(Err(_))));
+    }
+}
@@ -0,0 +1,73 @@
+// A minimal HTTP server for tests, standing in for adventofcode.com
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread;
+use std::thread::JoinHandle;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub cookie: Option<String>,
+    pub body: String,
+}
+
+// Serves one response per route, then hands back the requests it received.
+// Unknown paths get a 404, and each still counts towards the number of routes
+pub fn serve(routes: Vec<(&'static str, u16, &'static str)>) -> (String, JoinHandle<Vec<Request>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let count = routes.len();
+    let routes: HashMap<_, _> = routes.into_iter()
+        .map(|(path, status, body)| (path, (status, body)))
+        .collect();
+    let handle = thread::spawn(move || {
+        let mut requests = Vec::new();
+        for _ in 0..count {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut parts = request_line.split_whitespace();
+            let method = parts.next().unwrap().to_string();
+            let path = parts.next().unwrap().to_string();
+            let mut cookie = None;
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+                let Some((name, value)) = header.split_once(':') else {
+                    continue;
+                };
+                match name.to_ascii_lowercase().as_str() {
+                    "cookie" => cookie = Some(value.trim().to_string()),
+                    "content-length" => content_length = value.trim().parse().unwrap(),
+                    _ => {}
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            let (status, response) = routes.get(path.as_str()).copied().unwrap_or((404, "Not found"));
+            write!(
+                stream,
+                "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                response.len(),
+                response
+            ).unwrap();
+            requests.push(Request {
+                method,
+                path,
+                cookie,
+                body: String::from_utf8(body).unwrap(),
+            });
+        }
+        requests
+    });
+    (base_url, handle)
+}
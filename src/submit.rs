@@ -0,0 +1,293 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use crate::fetch::{USER_AGENT, YEAR};
+use crate::input::inputs_dir;
+use crate::solution::Answer;
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    TooRecent,
+    AlreadySolved,
+    Unrecognised,
+}
+
+impl Verdict {
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            Verdict::Correct => "That's the right answer",
+            Verdict::Wrong => "That's not the right answer",
+            Verdict::TooHigh => "That's not the right answer, it's too high",
+            Verdict::TooLow => "That's not the right answer, it's too low",
+            Verdict::TooRecent => "An answer was given too recently, nothing was checked",
+            Verdict::AlreadySolved => "This part has already been solved",
+            Verdict::Unrecognised => "The server's response wasn't recognised",
+        };
+        write!(f, "{}", description)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Attempt {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    pub submitted_at: u64,
+    // Seconds since the epoch before which the server won't accept another answer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<u64>,
+}
+
+// Every submission ever made, stored as a TOML array of [[attempt]] tables
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct History {
+    #[serde(default, rename = "attempt")]
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(History::default());
+        }
+        let contents = fs::read_to_string(path)
+            .map_err(|error| format!("Unable to read {}: {}", path.display(), error))?;
+        toml::from_str(&contents)
+            .map_err(|error| format!("Unable to parse {}: {}", path.display(), error))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let contents = toml::to_string(self).map_err(|error| error.to_string())?;
+        fs::write(path, contents)
+            .map_err(|error| format!("Unable to write {}: {}", path.display(), error))
+    }
+
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    pub fn record(&mut self, attempt: Attempt) {
+        self.attempts.push(attempt);
+    }
+
+    // Why an answer shouldn't be sent, given everything submitted so far
+    pub fn refusal(&self, day: u8, part: u8, answer: &str, now: u64) -> Option<String> {
+        let for_part = || self.attempts.iter().filter(|attempt| attempt.day == day && attempt.part == part);
+        if let Some(correct) = for_part().find(|attempt| attempt.verdict == Verdict::Correct) {
+            return Some(format!("Day {} part {} was already solved with {}", day, part, correct.answer));
+        }
+        if let Some(wrong) = for_part().find(|attempt| attempt.answer == answer && attempt.verdict.is_wrong()) {
+            return Some(format!("{} was already rejected for day {} part {}: {}", answer, day, part, wrong.verdict));
+        }
+        // The server's cooldown applies to the whole account, not just one puzzle
+        let retry_after = self.attempts.iter().filter_map(|attempt| attempt.retry_after).max()?;
+        if retry_after > now {
+            return Some(format!("The server asked us to wait another {}s before answering again", retry_after - now));
+        }
+        None
+    }
+}
+
+pub fn default_history_path() -> PathBuf {
+    inputs_dir().join("submissions.toml")
+}
+
+pub struct Submitter {
+    base_url: String,
+    session: String,
+    history_path: PathBuf,
+}
+
+impl Submitter {
+    pub fn new(base_url: &str, session: &str, history_path: &Path) -> Self {
+        Submitter {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            history_path: history_path.to_path_buf(),
+        }
+    }
+
+    // Posts an answer unless the history says it's pointless, and records what the server said
+    pub fn submit(&self, day: u8, part: u8, answer: &Answer) -> Result<Attempt, String> {
+        let answer = answer.to_string();
+        let mut history = History::load(&self.history_path)?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_secs())
+            .unwrap_or(0);
+        if let Some(reason) = history.refusal(day, part, &answer, now) {
+            return Err(reason);
+        }
+
+        let url = format!("{}/{}/day/{}/answer", self.base_url, YEAR, day);
+        let response = ureq::post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .send_form(&[("level", &part.to_string()), ("answer", &answer)])
+            .map_err(|error| format!("Submit failed: {}", error))?
+            .into_string()
+            .map_err(|error| format!("Unable to read the response from {}: {}", url, error))?;
+
+        let (verdict, wait) = parse_response(&response);
+        let attempt = Attempt {
+            day,
+            part,
+            answer,
+            verdict,
+            submitted_at: now,
+            retry_after: wait.map(|wait| now + wait.as_secs()),
+        };
+        history.record(attempt.clone());
+        history.save(&self.history_path)?;
+        Ok(attempt)
+    }
+}
+
+fn parse_response(response: &str) -> (Verdict, Option<Duration>) {
+    let verdict = if response.contains("That's the right answer") {
+        Verdict::Correct
+    } else if response.contains("That's not the right answer") {
+        if response.contains("too high") {
+            Verdict::TooHigh
+        } else if response.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else if response.contains("You gave an answer too recently") {
+        Verdict::TooRecent
+    } else if response.contains("You don't seem to be solving the right level") {
+        Verdict::AlreadySolved
+    } else {
+        Verdict::Unrecognised
+    };
+    (verdict, parse_wait(response))
+}
+
+// Too recent: "You have 1m 23s left to wait". Wrong: "please wait 5 minutes before trying again"
+fn parse_wait(response: &str) -> Option<Duration> {
+    let left_to_wait = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+    if let Some(captures) = left_to_wait.captures(response) {
+        let minutes = captures.get(1).map_or(0, |minutes| minutes.as_str().parse::<u64>().unwrap());
+        let seconds = captures[2].parse::<u64>().unwrap();
+        return Some(Duration::from_secs(minutes * 60 + seconds));
+    }
+    let before_trying_again = Regex::new(r"wait (one|\d+) minutes? before trying again").unwrap();
+    let captures = before_trying_again.captures(response)?;
+    let minutes = match &captures[1] {
+        "one" => 1,
+        minutes => minutes.parse::<u64>().unwrap(),
+    };
+    Some(Duration::from_secs(minutes * 60))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use crate::stub_server::serve;
+    use super::*;
+
+    const CORRECT: &str = "<article><p>That's the right answer! You are one gold star closer.</p></article>";
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high. Please wait one minute before trying again.</p></article>";
+
+    fn attempt(answer: &str, verdict: Verdict, retry_after: Option<u64>) -> Attempt {
+        Attempt {
+            day: 1,
+            part: 1,
+            answer: answer.to_string(),
+            verdict,
+            submitted_at: 100,
+            retry_after,
+        }
+    }
+
+    #[test]
+    fn test_submit() {
+        let (base_url, server) = serve(vec![("/2024/day/1/answer", 200, CORRECT)]);
+        let dir = tempfile::tempdir().unwrap();
+        let history_path = dir.path().join("submissions.toml");
+        let attempt = Submitter::new(&base_url, "secret", &history_path)
+            .submit(1, 2, &Answer::U64(31))
+            .unwrap();
+        assert_eq!(attempt.verdict, Verdict::Correct);
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].cookie.as_deref(), Some("session=secret"));
+        assert_eq!(requests[0].body, "level=2&answer=31");
+        assert_eq!(History::load(&history_path).unwrap().attempts(), &[attempt]);
+    }
+
+    #[test]
+    fn test_submit_records_wrong_answers() {
+        let (base_url, server) = serve(vec![("/2024/day/1/answer", 200, TOO_HIGH)]);
+        let dir = tempfile::tempdir().unwrap();
+        let history_path = dir.path().join("submissions.toml");
+        let submitter = Submitter::new(&base_url, "secret", &history_path);
+        let attempt = submitter.submit(1, 1, &Answer::U64(500)).unwrap();
+        assert_eq!(attempt.verdict, Verdict::TooHigh);
+        assert_eq!(attempt.retry_after, Some(attempt.submitted_at + 60));
+        server.join().unwrap();
+
+        // The server has stopped listening, so this only passes if nothing is sent
+        let error = submitter.submit(1, 1, &Answer::U64(500)).unwrap_err();
+        assert!(error.contains("already rejected"), "{}", error);
+    }
+
+    #[rstest]
+    #[case(vec![attempt("10", Verdict::Correct, None)], "11", 200, Some("already solved"))]
+    #[case(vec![attempt("10", Verdict::TooLow, None)], "10", 200, Some("already rejected"))]
+    #[case(vec![attempt("10", Verdict::TooLow, None)], "11", 200, None)]
+    #[case(vec![attempt("10", Verdict::TooRecent, None)], "10", 200, None)]
+    #[case(vec![attempt("10", Verdict::Wrong, Some(160))], "11", 130, Some("another 30s"))]
+    #[case(vec![attempt("10", Verdict::Wrong, Some(160))], "11", 160, None)]
+    fn test_refusal(#[case] attempts: Vec<Attempt>, #[case] answer: &str, #[case] now: u64, #[case] expected: Option<&str>) {
+        let history = History { attempts };
+        let refusal = history.refusal(1, 1, answer, now);
+        match expected {
+            Some(expected) => assert!(refusal.as_deref().unwrap_or("").contains(expected), "{:?}", refusal),
+            None => assert_eq!(refusal, None),
+        }
+    }
+
+    #[rstest]
+    #[case(CORRECT, Verdict::Correct, None)]
+    #[case(TOO_HIGH, Verdict::TooHigh, Some(60))]
+    #[case("That's not the right answer; your answer is too low. Please wait 5 minutes before trying again.", Verdict::TooLow, Some(300))]
+    #[case("That's not the right answer. Please wait one minute before trying again.", Verdict::Wrong, Some(60))]
+    #[case("You gave an answer too recently. You have 1m 23s left to wait.", Verdict::TooRecent, Some(83))]
+    #[case("You gave an answer too recently. You have 45s left to wait.", Verdict::TooRecent, Some(45))]
+    #[case("You don't seem to be solving the right level. Did you already complete it?", Verdict::AlreadySolved, None)]
+    #[case("<html>Puzzle inputs differ by user.</html>", Verdict::Unrecognised, None)]
+    fn test_parse_response(#[case] response: &str, #[case] verdict: Verdict, #[case] wait: Option<u64>) {
+        assert_eq!(parse_response(response), (verdict, wait.map(Duration::from_secs)));
+    }
+
+    #[test]
+    fn test_history_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("submissions.toml");
+        let mut history = History::load(&path).unwrap();
+        history.record(attempt("10", Verdict::TooLow, Some(160)));
+        history.record(attempt("12", Verdict::Correct, None));
+        history.save(&path).unwrap();
+        assert_eq!(History::load(&path).unwrap().attempts(), history.attempts());
+    }
+}
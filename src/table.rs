@@ -0,0 +1,107 @@
+use std::time::Duration;
+use crate::solution::Measurement;
+use crate::timing::format_duration;
+
+// What running one day and part came to, for a line of the summary table
+pub enum Row {
+    NoExample,
+    NoInput,
+    ParseError,
+    Measured(Measurement),
+}
+
+// The summary table for several days. When benchmarking, the solve times are shown as the min,
+// median and max of the runs, otherwise the median times are totalled at the bottom
+pub fn render(rows: &[(u8, u8, Row)], bench: bool) -> String {
+    let mut table = if bench {
+        format!("{:>3}  {:>4}  {:>20}  {:>10}  {:>10}  {:>10}  {:>10}\n", "Day", "Part", "Answer", "Parse", "Min", "Median", "Max")
+    } else {
+        format!("{:>3}  {:>4}  {:>20}  {:>10}  {:>10}\n", "Day", "Part", "Answer", "Parse", "Solve")
+    };
+    let mut total_parse = Duration::ZERO;
+    let mut total_solve = Duration::ZERO;
+    for (day, part, row) in rows {
+        let measurement = match row {
+            Row::NoExample => {
+                table.push_str(&format!("{:>3}  {:>4}  {:>20}\n", day, part, "no example"));
+                continue;
+            }
+            Row::NoInput => {
+                table.push_str(&format!("{:>3}  {:>4}  {:>20}\n", day, part, "no input"));
+                continue;
+            }
+            Row::ParseError => {
+                table.push_str(&format!("{:>3}  {:>4}  {:>20}\n", day, part, "parse error"));
+                continue;
+            }
+            Row::Measured(measurement) => measurement,
+        };
+        total_parse += measurement.parse.median;
+        total_solve += measurement.solve.median;
+        let parse = format_duration(measurement.parse.median);
+        if bench {
+            table.push_str(&format!(
+                "{:>3}  {:>4}  {:>20}  {:>10}  {:>10}  {:>10}  {:>10}\n",
+                day,
+                part,
+                measurement.answer,
+                parse,
+                format_duration(measurement.solve.min),
+                format_duration(measurement.solve.median),
+                format_duration(measurement.solve.max)
+            ));
+        } else {
+            table.push_str(&format!(
+                "{:>3}  {:>4}  {:>20}  {:>10}  {:>10}\n",
+                day,
+                part,
+                measurement.answer,
+                parse,
+                format_duration(measurement.solve.median)
+            ));
+        }
+    }
+    if !bench {
+        table.push_str(&format!("{:>3}  {:>4}  {:>20}  {:>10}  {:>10}\n", "", "", "Total", format_duration(total_parse), format_duration(total_solve)));
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solution::Answer;
+    use crate::timing::Stats;
+    use super::*;
+
+    fn measured(answer: u64, parse: u64, solve: [u64; 3]) -> Row {
+        Row::Measured(Measurement {
+            answer: Answer::U64(answer),
+            parse: Stats::from_samples(vec![Duration::from_micros(parse)]).unwrap(),
+            solve: Stats::from_samples(solve.map(Duration::from_micros).to_vec()).unwrap(),
+        })
+    }
+
+    #[test]
+    fn test_render() {
+        let rows = vec![(1, 1, measured(11, 100, [200, 300, 400])), (1, 2, Row::ParseError), (2, 1, measured(2, 50, [5, 5, 5]))];
+        assert_eq!(render(&rows, false), [
+            "Day  Part                Answer       Parse       Solve",
+            "  1     1                    11       100µs       300µs",
+            "  1     2           parse error",
+            "  2     1                     2        50µs         5µs",
+            "                          Total       150µs       305µs",
+            "",
+        ].join("\n"));
+    }
+
+    #[test]
+    fn test_render_bench() {
+        let rows = vec![(3, 2, measured(48, 10, [20, 30, 40])), (4, 1, Row::NoExample)];
+        assert_eq!(render(&rows, true), [
+            "Day  Part                Answer       Parse         Min      Median         Max",
+            "  3     2                    48        10µs        20µs        30µs        40µs",
+            "  4     1            no example",
+            "",
+        ].join("\n"));
+    }
+}
@@ -0,0 +1,82 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort();
+        let middle = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[middle - 1] + samples[middle]) / 2
+        } else {
+            samples[middle]
+        };
+        Some(Stats {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        })
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {} / median {} / max {}",
+            format_duration(self.min),
+            format_duration(self.median),
+            format_duration(self.max)
+        )
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_secs_f64() * 1_000_000.0;
+    if micros < 1_000.0 {
+        format!("{:.0}µs", micros)
+    } else if micros < 1_000_000.0 {
+        format!("{:.2}ms", micros / 1_000.0)
+    } else {
+        format!("{:.2}s", micros / 1_000_000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use super::*;
+
+    #[rstest]
+    #[case(Duration::from_micros(250), "250µs")]
+    #[case(Duration::from_micros(12_345), "12.35ms")]
+    #[case(Duration::from_millis(2_500), "2.50s")]
+    fn test_format_duration(#[case] duration: Duration, #[case] expected: &str) {
+        assert_eq!(format_duration(duration), expected);
+    }
+
+    #[rstest]
+    #[case(vec![3, 1, 2], Some((1, 2, 3)))]
+    #[case(vec![6, 1, 4, 2], Some((1, 3, 6)))]
+    #[case(vec![7], Some((7, 7, 7)))]
+    #[case(vec![], None)]
+    fn test_stats_from_samples(#[case] samples: Vec<u64>, #[case] expected: Option<(u64, u64, u64)>) {
+        let samples = samples.into_iter().map(Duration::from_millis).collect();
+        let expected = expected.map(|(min, median, max)| Stats {
+            min: Duration::from_millis(min),
+            median: Duration::from_millis(median),
+            max: Duration::from_millis(max),
+        });
+        assert_eq!(Stats::from_samples(samples), expected);
+    }
+}
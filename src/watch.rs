@@ -0,0 +1,162 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+use serde_json::Value;
+use crate::input::example_files_in;
+use crate::timing::format_duration;
+
+pub fn watched_files(root: &Path, day: u8) -> Vec<PathBuf> {
+    let inputs = root.join("inputs");
+    let mut files = vec![
+        root.join("src").join(format!("day_{}.rs", day)),
+        inputs.join(format!("day-{}-input.txt", day)),
+    ];
+    files.extend(example_files_in(&inputs, day));
+    files
+}
+
+// Missing files are included, so creating or deleting one counts as a change
+fn modification_times(files: &[PathBuf]) -> Vec<(PathBuf, Option<SystemTime>)> {
+    files.iter()
+        .map(|file| (file.clone(), fs::metadata(file).and_then(|metadata| metadata.modified()).ok()))
+        .collect()
+}
+
+// Polls the day's files and calls `on_change` once at the start and again after every change.
+// Polling rather than inotify keeps this dependency free and working on every platform
+pub fn watch(root: &Path, day: u8, interval: Duration, mut on_change: impl FnMut()) -> ! {
+    let mut last_seen = modification_times(&watched_files(root, day));
+    on_change();
+    loop {
+        thread::sleep(interval);
+        let current = modification_times(&watched_files(root, day));
+        if current != last_seen {
+            last_seen = current;
+            on_change();
+        }
+    }
+}
+
+// Runs the day's tests and then the solution on the real input, each through cargo so that
+// edits are compiled, and sums both up on one line
+pub fn check(root: &Path, day: u8) -> String {
+    let tests = cargo(root, &["test", "--lib", "--quiet", &format!("day_{}::", day)]);
+    let solution = cargo(root, &["run", "--release", "--quiet", "--", "--days", &day.to_string(), "--format", "json"]);
+    let mut line = match &tests {
+        Ok(output) => describe_tests(output),
+        Err(error) => error.clone(),
+    };
+    match &solution {
+        Ok(output) => {
+            for record in output.lines().filter_map(|line| serde_json::from_str::<Value>(line).ok()) {
+                line.push_str(" | ");
+                line.push_str(&describe_record(&record));
+            }
+        }
+        Err(error) => {
+            line.push_str(" | ");
+            line.push_str(error);
+        }
+    }
+    line
+}
+
+// Stdout of a successful or test-failure run, or a short reason it didn't get that far
+fn cargo(root: &Path, args: &[&str]) -> Result<String, String> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = Command::new(cargo)
+        .args(args)
+        .current_dir(root)
+        .output()
+        .map_err(|error| format!("couldn't run cargo: {}", error))?;
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stderr.contains("could not compile") {
+        let first_error = stderr.lines().find(|line| line.starts_with("error")).unwrap_or("build failed");
+        return Err(first_error.to_string());
+    }
+    Ok(stdout)
+}
+
+fn describe_tests(output: &str) -> String {
+    let (passed, failed) = output.lines()
+        .filter_map(|line| line.strip_prefix("test result: "))
+        .map(|summary| (count(summary, "passed"), count(summary, "failed")))
+        .fold((0, 0), |(passed, failed), counts| (passed + counts.0, failed + counts.1));
+    if failed > 0 {
+        format!("tests: {} passed, {} FAILED", passed, failed)
+    } else {
+        format!("tests: {} passed", passed)
+    }
+}
+
+// Finds the number before `label` in a summary like "ok. 3 passed; 0 failed; 0 ignored"
+fn count(summary: &str, label: &str) -> usize {
+    summary.split(';')
+        .filter_map(|part| part.trim().trim_start_matches("ok. ").trim_start_matches("FAILED. ").split_once(' '))
+        .find(|(_, name)| *name == label)
+        .and_then(|(number, _)| number.parse().ok())
+        .unwrap_or(0)
+}
+
+fn describe_record(record: &Value) -> String {
+    let part = &record["part"];
+    match record["status"].as_str() {
+        Some("solved") => {
+            let solve = Duration::from_nanos(record["timings"]["solve"]["median_ns"].as_u64().unwrap_or(0));
+            format!("part {}: {} in {}", part, record["answer"], format_duration(solve))
+        }
+        Some("parse_error") => {
+            let error = &record["error"];
+            format!("part {}: parse error at {}:{}", part, error["line"], error["column"])
+        }
+        _ => format!("part {}: no input", part),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use super::*;
+
+    #[test]
+    fn test_watched_files() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let files = watched_files(root, 12);
+        assert_eq!(files, vec![
+            root.join("src/day_12.rs"),
+            root.join("inputs/day-12-input.txt"),
+            root.join("inputs/day-12-example-1.txt"),
+            root.join("inputs/day-12-example-2.txt"),
+            root.join("inputs/day-12-example-3.txt"),
+        ]);
+    }
+
+    #[test]
+    fn test_modification_times_notice_new_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = vec![dir.path().join("day_1.rs")];
+        let before = modification_times(&files);
+        fs::write(&files[0], "").unwrap();
+        assert_ne!(modification_times(&files), before);
+    }
+
+    #[rstest]
+    #[case("test result: ok. 3 passed; 0 failed; 0 ignored; 0 measured; 164 filtered out; finished in 0.02s\n", "tests: 3 passed")]
+    #[case("test result: FAILED. 1 passed; 2 failed; 0 ignored; 0 measured; 0 filtered out\n", "tests: 1 passed, 2 FAILED")]
+    #[case("", "tests: 0 passed")]
+    fn test_describe_tests(#[case] output: &str, #[case] expected: &str) {
+        assert_eq!(describe_tests(output), expected);
+    }
+
+    #[rstest]
+    #[case(r#"{"day":1,"part":1,"status":"solved","answer":11,"timings":{"solve":{"median_ns":2500}}}"#, "part 1: 11 in 2µs")]
+    #[case(r#"{"day":1,"part":2,"status":"parse_error","error":{"line":2,"column":5}}"#, "part 2: parse error at 2:5")]
+    #[case(r#"{"day":1,"part":1,"status":"missing_input"}"#, "part 1: no input")]
+    fn test_describe_record(#[case] record: &str, #[case] expected: &str) {
+        assert_eq!(describe_record(&serde_json::from_str(record).unwrap()), expected);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::parse_error::ParseError;
+use crate::solution::{Answer, Solution};
+
+pub struct Day{{day}};
+
+impl Solution for Day{{day}} {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(_input: &Self::Input) -> Answer {
+        Answer::String("unsolved".to_string())
+    }
+
+    fn part_2(_input: &Self::Input) -> Answer {
+        Answer::String("unsolved".to_string())
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<String>, ParseError> {
+    Ok(input.lines().map(String::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::read_to_string;
+    use rstest::rstest;
+    use super::*;
+
+    #[rstest]
+    #[case("inputs/day-{{day}}-example.txt", Answer::U64(0))]
+    #[ignore = "the example's answer still needs filling in"]
+    fn test_part_1_example(#[case] input_file: &str, #[case] expected: Answer) {
+        let input = parse_input(&read_to_string(input_file).unwrap()).unwrap();
+        assert_eq!(Day{{day}}::part_1(&input), expected);
+    }
+
+    #[rstest]
+    #[case("inputs/day-{{day}}-example.txt", Answer::U64(0))]
+    #[ignore = "the example's answer still needs filling in"]
+    fn test_part_2_example(#[case] input_file: &str, #[case] expected: Answer) {
+        let input = parse_input(&read_to_string(input_file).unwrap()).unwrap();
+        assert_eq!(Day{{day}}::part_2(&input), expected);
+    }
+}
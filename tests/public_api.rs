@@ -0,0 +1,32 @@
+use std::fs::read_to_string;
+use advent_of_code_2024::day_13::Day13;
+use advent_of_code_2024::day_9::Day9;
+use advent_of_code_2024::solution::{find_day, measure, Answer, Solution};
+
+#[test]
+fn test_filesystem_from_outside_the_crate() {
+    let filesystem = Day9::parse(&read_to_string("inputs/day-9-example.txt").unwrap()).unwrap();
+    assert_eq!(filesystem.compact().checksum(), 1928);
+    assert_eq!(filesystem.compact_v2().checksum(), 2858);
+}
+
+#[test]
+fn test_machines_from_outside_the_crate() {
+    let machines = Day13::parse(&read_to_string("inputs/day-13-example.txt").unwrap()).unwrap();
+    let solutions = machines.iter().map(|machine| machine.solve()).collect::<Vec<_>>();
+    assert_eq!(solutions, vec![Some((80, 40)), None, Some((38, 86)), None]);
+}
+
+#[test]
+fn test_run_through_the_registry() {
+    let input = read_to_string("inputs/day-1-example.txt").unwrap();
+    let run = find_day(1).unwrap().run(&input, 2).unwrap().unwrap();
+    assert_eq!(run.answer, Answer::U64(31));
+}
+
+#[test]
+fn test_measure_through_the_registry() {
+    let input = read_to_string("inputs/day-9-example.txt").unwrap();
+    let measurement = measure(find_day(9).unwrap(), &input, 1, 3).unwrap().unwrap();
+    assert_eq!(measurement.answer, Answer::U64(1928));
+}